@@ -0,0 +1,454 @@
+//! Snapshot store for every file this app modifies.
+//!
+//! Before a managed file is overwritten or deleted its current content is
+//! copied into `<app data>/snapshots/objects/<sha256>`, and a record keyed by
+//! the original path is appended to `snapshots/index.json`. Identical content
+//! is stored once, and nothing is ever written next to the original file.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::error::{AppError, AppResult};
+use crate::{config, files, tools};
+
+const LEGACY_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// Serialises read-modify-write cycles on the index.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Save,
+    Delete,
+    Restore,
+    Import,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Snapshot {
+    id: String,
+    /// Absolute path of the file the snapshot was taken from.
+    path: String,
+    tool: Option<String>,
+    hash: String,
+    size: u64,
+    timestamp: i64,
+    action: Action,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RetentionPolicy {
+    /// Snapshots kept per file.
+    #[serde(rename = "maxPerFile")]
+    pub max_per_file: usize,
+    /// Snapshots older than this are dropped, except each file's newest.
+    #[serde(rename = "maxAgeDays")]
+    pub max_age_days: Option<u32>,
+    /// Upper bound on stored blob bytes; oldest snapshots go first.
+    #[serde(rename = "maxTotalBytes")]
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy {
+            max_per_file: 20,
+            max_age_days: Some(90),
+            max_total_bytes: Some(100 * 1024 * 1024),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotContent {
+    snapshot: Snapshot,
+    content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    id: String,
+    diff: String,
+    additions: usize,
+    deletions: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SnapshotHistory {
+    entries: Vec<Snapshot>,
+    total: usize,
+    limit: usize,
+    offset: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImportResult {
+    imported: usize,
+    removed: usize,
+}
+
+// ============================================================================
+// Store
+// ============================================================================
+
+fn store_dir() -> AppResult<PathBuf> {
+    Ok(config::data_dir()?.join("snapshots"))
+}
+
+fn object_path(store: &Path, hash: &str) -> PathBuf {
+    store.join("objects").join(&hash[..2]).join(hash)
+}
+
+fn read_object(store: &Path, hash: &str) -> AppResult<Vec<u8>> {
+    let object = object_path(store, hash);
+    fs::read(&object).map_err(|e| AppError::io(e, &object))
+}
+
+fn load_index(store: &Path) -> Vec<Snapshot> {
+    fs::read_to_string(store.join("index.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(store: &Path, index: &[Snapshot]) -> AppResult<()> {
+    let json = serde_json::to_string(index).map_err(|e| AppError::other(e.to_string()))?;
+    files::write_atomic(&store.join("index.json"), json.as_bytes()).map(|_| ())
+}
+
+fn tool_for(path: &Path) -> Option<String> {
+    tools::adapters()
+        .iter()
+        .find(|a| a.root_dir().map(|root| path.starts_with(root)).unwrap_or(false))
+        .map(|a| a.id().to_string())
+}
+
+fn store_blob(store: &Path, content: &[u8]) -> AppResult<String> {
+    let hash = files::content_hash(content);
+    let object = object_path(store, &hash);
+    if !object.exists() {
+        let dir = object.parent().unwrap();
+        fs::create_dir_all(dir).map_err(|e| AppError::io(e, dir))?;
+        fs::write(&object, content).map_err(|e| AppError::io(e, &object))?;
+    }
+    Ok(hash)
+}
+
+fn record(
+    store: &Path,
+    index: &mut Vec<Snapshot>,
+    path: &Path,
+    content: &[u8],
+    timestamp: i64,
+    action: Action,
+) -> AppResult<()> {
+    let path_str = path.to_string_lossy().to_string();
+    let hash = store_blob(store, content)?;
+
+    // Saving the same content twice in a row adds nothing to the history.
+    let latest = index.iter().rev().find(|s| s.path == path_str);
+    if latest.map(|s| s.hash == hash).unwrap_or(false) {
+        return Ok(());
+    }
+
+    index.push(Snapshot {
+        id: format!("{}-{}", timestamp, &hash[..8]),
+        path: path_str,
+        tool: tool_for(path),
+        hash,
+        size: content.len() as u64,
+        timestamp,
+        action,
+    });
+    Ok(())
+}
+
+/// Drops snapshots outside the retention policy.
+fn prune(index: &mut Vec<Snapshot>, policy: &RetentionPolicy) {
+    index.sort_by_key(|s| s.timestamp);
+
+    // Per-file count, newest kept
+    let mut seen: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut keep = vec![true; index.len()];
+    for (i, snap) in index.iter().enumerate().rev() {
+        let count = seen.entry(snap.path.clone()).or_default();
+        *count += 1;
+        if *count > policy.max_per_file.max(1) {
+            keep[i] = false;
+        }
+    }
+
+    // Age, always sparing each file's newest snapshot
+    if let Some(days) = policy.max_age_days {
+        let cutoff = chrono::Utc::now().timestamp_millis() - i64::from(days) * 86_400_000;
+        let mut newest_seen = std::collections::HashSet::new();
+        for (i, snap) in index.iter().enumerate().rev() {
+            let is_newest = newest_seen.insert(snap.path.clone());
+            if !is_newest && snap.timestamp < cutoff {
+                keep[i] = false;
+            }
+        }
+    }
+
+    let mut iter = keep.into_iter();
+    index.retain(|_| iter.next().unwrap_or(true));
+
+    // Total size, counting each blob once
+    if let Some(max_bytes) = policy.max_total_bytes {
+        loop {
+            let total: u64 = index
+                .iter()
+                .map(|s| (&s.hash, s.size))
+                .collect::<std::collections::HashMap<_, _>>()
+                .values()
+                .sum();
+            if total <= max_bytes || index.is_empty() {
+                break;
+            }
+            index.remove(0);
+        }
+    }
+}
+
+/// Deletes blobs no longer referenced by the (already saved) index.
+fn collect_garbage(store: &Path, index: &[Snapshot]) {
+    let live: std::collections::HashSet<&str> = index.iter().map(|s| s.hash.as_str()).collect();
+    if let Ok(shards) = fs::read_dir(store.join("objects")) {
+        for shard in shards.filter_map(|e| e.ok()) {
+            if let Ok(objects) = fs::read_dir(shard.path()) {
+                for object in objects.filter_map(|e| e.ok()) {
+                    if !live.contains(object.file_name().to_string_lossy().as_ref()) {
+                        let _ = fs::remove_file(object.path());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Snapshots `path` into the store before the app changes it. Missing files
+/// (e.g. ones about to be created) are skipped.
+pub fn create_backup(path: &Path, action: Action) -> AppResult<()> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let content = fs::read(path).map_err(|e| AppError::io(e, path))?;
+    let store = store_dir()?;
+    fs::create_dir_all(&store).map_err(|e| AppError::io(e, &store))?;
+
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&store);
+    record(
+        &store,
+        &mut index,
+        path,
+        &content,
+        chrono::Utc::now().timestamp_millis(),
+        action,
+    )?;
+    prune(&mut index, &config::current().snapshots);
+    save_index(&store, &index)?;
+    collect_garbage(&store, &index);
+    Ok(())
+}
+
+fn find_snapshot(id: &str) -> AppResult<(PathBuf, Snapshot)> {
+    let store = store_dir()?;
+    let snapshot = load_index(&store)
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| AppError::not_found(format!("Snapshot not found: {}", id)))?;
+    Ok((store, snapshot))
+}
+
+fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str) -> (String, usize, usize) {
+    let diff = similar::TextDiff::from_lines(old, new);
+    let mut additions = 0;
+    let mut deletions = 0;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            similar::ChangeTag::Insert => additions += 1,
+            similar::ChangeTag::Delete => deletions += 1,
+            similar::ChangeTag::Equal => {}
+        }
+    }
+    let text = diff
+        .unified_diff()
+        .context_radius(3)
+        .header(old_name, new_name)
+        .to_string();
+    (text, additions, deletions)
+}
+
+/// Finds `<name>.backup.<timestamp>` files left behind by older versions.
+fn collect_legacy_backups(dir: &Path, found: &mut Vec<(PathBuf, PathBuf, i64)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !name.starts_with('.') || name == ".system" {
+                collect_legacy_backups(&path, found);
+            }
+        } else if let Some((original, stamp)) = name.rsplit_once(".backup.") {
+            if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(stamp, LEGACY_TIMESTAMP_FORMAT) {
+                found.push((path.clone(), dir.join(original), dt.and_utc().timestamp_millis()));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Snapshots of one file, newest first.
+#[tauri::command]
+pub fn backups_list(path: String) -> AppResult<Vec<Snapshot>> {
+    let mut snapshots: Vec<Snapshot> = load_index(&store_dir()?)
+        .into_iter()
+        .filter(|s| s.path == path)
+        .collect();
+    snapshots.reverse();
+    Ok(snapshots)
+}
+
+/// Every change this app has made, newest first.
+#[tauri::command]
+pub fn backups_history(
+    limit: Option<usize>,
+    offset: Option<usize>,
+    tool: Option<String>,
+) -> AppResult<SnapshotHistory> {
+    let limit = limit.unwrap_or(50);
+    let offset = offset.unwrap_or(0);
+
+    let mut entries = load_index(&store_dir()?);
+    if let Some(ref tool) = tool {
+        entries.retain(|s| s.tool.as_ref() == Some(tool));
+    }
+    entries.reverse();
+
+    let total = entries.len();
+    let entries = entries.into_iter().skip(offset).take(limit).collect();
+    Ok(SnapshotHistory {
+        entries,
+        total,
+        limit,
+        offset,
+    })
+}
+
+#[tauri::command]
+pub fn backups_read(id: String) -> AppResult<SnapshotContent> {
+    let (store, snapshot) = find_snapshot(&id)?;
+    let bytes = read_object(&store, &snapshot.hash)?;
+    Ok(SnapshotContent {
+        snapshot,
+        content: String::from_utf8_lossy(&bytes).to_string(),
+    })
+}
+
+/// Unified diff from the snapshot to the file as it is on disk now.
+#[tauri::command]
+pub fn backups_diff(id: String) -> AppResult<SnapshotDiff> {
+    let (store, snapshot) = find_snapshot(&id)?;
+    let old = read_object(&store, &snapshot.hash)?;
+    let current = fs::read(&snapshot.path).unwrap_or_default();
+    let name = Path::new(&snapshot.path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (diff, additions, deletions) = unified_diff(
+        &String::from_utf8_lossy(&old),
+        &String::from_utf8_lossy(&current),
+        &format!("{} ({})", name, id),
+        &name,
+    );
+    Ok(SnapshotDiff {
+        id,
+        diff,
+        additions,
+        deletions,
+    })
+}
+
+/// Writes a snapshot back to its original path, snapshotting the current
+/// file first so the restore itself can be undone.
+#[tauri::command]
+pub fn backups_restore(id: String) -> AppResult<bool> {
+    let (store, snapshot) = find_snapshot(&id)?;
+    let content = read_object(&store, &snapshot.hash)?;
+    let target = PathBuf::from(&snapshot.path);
+    create_backup(&target, Action::Restore)?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::io(e, parent))?;
+    }
+    files::write_atomic(&target, &content)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn backups_get_retention() -> RetentionPolicy {
+    config::current().snapshots
+}
+
+#[tauri::command]
+pub fn backups_set_retention(policy: RetentionPolicy) -> AppResult<RetentionPolicy> {
+    let mut app_config = config::current();
+    app_config.snapshots = policy.clone();
+    config::save(app_config)?;
+
+    let store = store_dir()?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&store);
+    prune(&mut index, &policy);
+    save_index(&store, &index)?;
+    collect_garbage(&store, &index);
+    Ok(policy)
+}
+
+/// Moves sibling `.backup.*` files from the tool directories into the store.
+#[tauri::command]
+pub fn backups_import_legacy() -> AppResult<ImportResult> {
+    let mut found = Vec::new();
+    for root in tools::adapters().iter().filter_map(|a| a.root_dir().ok()) {
+        collect_legacy_backups(&root, &mut found);
+    }
+    found.sort_by_key(|(_, _, ts)| *ts);
+
+    let store = store_dir()?;
+    fs::create_dir_all(&store).map_err(|e| AppError::io(e, &store))?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&store);
+
+    let mut imported = 0;
+    let mut stored = Vec::new();
+    for (backup, original, timestamp) in found {
+        let Ok(content) = fs::read(&backup) else {
+            continue;
+        };
+        let before = index.len();
+        record(&store, &mut index, &original, &content, timestamp, Action::Import)?;
+        imported += index.len() - before;
+        stored.push(backup);
+    }
+
+    index.sort_by_key(|s| s.timestamp);
+    prune(&mut index, &config::current().snapshots);
+    save_index(&store, &index)?;
+    collect_garbage(&store, &index);
+
+    // Only delete the originals once the index is safely on disk
+    let removed = stored.iter().filter(|p| fs::remove_file(p).is_ok()).count();
+    Ok(ImportResult { imported, removed })
+}
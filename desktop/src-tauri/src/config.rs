@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use crate::backups::RetentionPolicy;
+use crate::error::{AppError, AppResult};
+use crate::files;
+use crate::tools::{self, ToolAdapter};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// App-level settings persisted as `config.json` in the app data dir.
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct AppConfig {
+    /// User-set tool roots keyed by tool id; these win over env vars.
+    #[serde(default, rename = "toolDirs")]
+    pub tool_dirs: HashMap<String, String>,
+    #[serde(default)]
+    pub snapshots: RetentionPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RootSource {
+    User,
+    Env,
+    Default,
+}
+
+pub struct ResolvedRoot {
+    pub path: PathBuf,
+    pub source: RootSource,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ToolDirInfo {
+    tool: String,
+    path: Option<String>,
+    source: Option<RootSource>,
+    exists: bool,
+    #[serde(rename = "userPath")]
+    user_path: Option<String>,
+    #[serde(rename = "envVar")]
+    env_var: Option<String>,
+    #[serde(rename = "envValue")]
+    env_value: Option<String>,
+    #[serde(rename = "defaultPath")]
+    default_path: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConfigView {
+    #[serde(rename = "configPath")]
+    config_path: Option<String>,
+    tools: Vec<ToolDirInfo>,
+}
+
+struct ConfigState {
+    path: Option<PathBuf>,
+    config: AppConfig,
+}
+
+static STATE: OnceLock<RwLock<ConfigState>> = OnceLock::new();
+
+fn state() -> &'static RwLock<ConfigState> {
+    STATE.get_or_init(|| {
+        RwLock::new(ConfigState {
+            path: None,
+            config: AppConfig::default(),
+        })
+    })
+}
+
+// ============================================================================
+// Loading & resolution
+// ============================================================================
+
+/// Loads `config.json` from the app data dir. Called once during setup; a
+/// missing or unreadable file leaves the defaults in place.
+pub fn init(data_dir: &Path) {
+    let path = data_dir.join("config.json");
+    let config = fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let mut guard = state().write().unwrap();
+    guard.path = Some(path);
+    guard.config = config;
+}
+
+pub fn current() -> AppConfig {
+    state().read().unwrap().config.clone()
+}
+
+/// Directory the app keeps its own files in (the folder holding `config.json`).
+pub fn data_dir() -> AppResult<PathBuf> {
+    state()
+        .read()
+        .unwrap()
+        .path
+        .as_ref()
+        .and_then(|p| p.parent().map(Path::to_path_buf))
+        .ok_or_else(|| AppError::other("App data directory is not initialised"))
+}
+
+pub fn save(config: AppConfig) -> AppResult<()> {
+    let mut guard = state().write().unwrap();
+    if let Some(path) = &guard.path {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(e, parent))?;
+        }
+        let json = serde_json::to_string_pretty(&config).map_err(|e| AppError::other(e.to_string()))?;
+        files::write_atomic(path, json.as_bytes())?;
+    }
+    guard.config = config;
+    Ok(())
+}
+
+/// Expands a leading `~` and rejects relative paths.
+fn normalize_dir(input: &str) -> AppResult<PathBuf> {
+    let trimmed = input.trim();
+    let home = || dirs::home_dir().ok_or_else(|| AppError::not_found("Could not find home directory"));
+    let path = if trimmed == "~" {
+        home()?
+    } else if let Some(rest) = trimmed.strip_prefix("~/") {
+        home()?.join(rest)
+    } else {
+        PathBuf::from(trimmed)
+    };
+    if !path.is_absolute() {
+        return Err(AppError::validation("path", format!("Path must be absolute: {}", input)));
+    }
+    Ok(path)
+}
+
+/// Resolves a tool's root: user-set path, then the tool's own env var, then
+/// `~/<default_dir>`.
+pub fn resolve_root<A: ToolAdapter + ?Sized>(adapter: &A) -> AppResult<ResolvedRoot> {
+    if let Some(user) = state().read().unwrap().config.tool_dirs.get(adapter.id()) {
+        return Ok(ResolvedRoot {
+            path: normalize_dir(user)?,
+            source: RootSource::User,
+        });
+    }
+
+    if let Some(value) = adapter
+        .env_var()
+        .and_then(|var| std::env::var(var).ok())
+        .filter(|v| !v.trim().is_empty())
+    {
+        return Ok(ResolvedRoot {
+            path: adapter.root_from_env(value.trim()),
+            source: RootSource::Env,
+        });
+    }
+
+    let home = dirs::home_dir().ok_or_else(|| {
+        AppError::not_found(format!(
+            "Could not find home directory; set a {} directory in settings",
+            adapter.name()
+        ))
+    })?;
+    Ok(ResolvedRoot {
+        path: home.join(adapter.default_dir()),
+        source: RootSource::Default,
+    })
+}
+
+fn describe(adapter: &dyn ToolAdapter) -> ToolDirInfo {
+    let resolved = resolve_root(adapter);
+    let env_var = adapter.env_var();
+    ToolDirInfo {
+        tool: adapter.id().to_string(),
+        path: resolved
+            .as_ref()
+            .ok()
+            .map(|r| r.path.to_string_lossy().to_string()),
+        source: resolved.as_ref().ok().map(|r| r.source),
+        exists: resolved.as_ref().map(|r| r.path.is_dir()).unwrap_or(false),
+        user_path: current().tool_dirs.get(adapter.id()).cloned(),
+        env_var: env_var.map(String::from),
+        env_value: env_var.and_then(|v| std::env::var(v).ok()),
+        default_path: dirs::home_dir().map(|h| h.join(adapter.default_dir()).to_string_lossy().to_string()),
+        error: resolved.err().map(|e| e.to_string()),
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn config_get() -> ConfigView {
+    let config_path = state()
+        .read()
+        .unwrap()
+        .path
+        .as_ref()
+        .map(|p| p.to_string_lossy().to_string());
+    ConfigView {
+        config_path,
+        tools: tools::adapters().iter().map(|a| describe(*a)).collect(),
+    }
+}
+
+/// Sets (or, with `path: None`, clears) the user override for a tool root.
+#[tauri::command]
+pub fn config_set_tool_dir(tool: String, path: Option<String>) -> AppResult<ConfigView> {
+    let adapter = tools::adapter(&tool)?;
+    let mut config = current();
+
+    match path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+        Some(p) => {
+            let dir = normalize_dir(p)?;
+            if dir.exists() && !dir.is_dir() {
+                return Err(AppError::validation("path", format!("Not a directory: {}", dir.display())));
+            }
+            config
+                .tool_dirs
+                .insert(adapter.id().to_string(), dir.to_string_lossy().to_string());
+        }
+        None => {
+            config.tool_dirs.remove(adapter.id());
+        }
+    }
+
+    save(config)?;
+    Ok(config_get())
+}
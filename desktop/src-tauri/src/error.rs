@@ -0,0 +1,185 @@
+//! The error type every command returns.
+//!
+//! Serialises as `{ "kind": "...", "message": "...", ...fields }` so the UI
+//! can branch on `kind` (e.g. highlight `line`/`column` for a parse error or
+//! offer a merge for a conflict) and still show `message` as-is.
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::files::FileVersion;
+
+pub type AppResult<T> = Result<T, AppError>;
+
+#[derive(Serialize, Debug)]
+pub struct Conflict {
+    pub message: String,
+    pub path: String,
+    pub expected: Option<FileVersion>,
+    /// `None` when the file was deleted underneath the editor.
+    pub actual: Option<FileVersion>,
+    /// Current on-disk content, for showing a merge.
+    pub content: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AppError {
+    NotFound {
+        message: String,
+        path: Option<String>,
+    },
+    /// The target changed underneath the caller or already exists.
+    Conflict(Box<Conflict>),
+    /// Input from the UI was rejected: malformed content, a bad name, etc.
+    Validation {
+        message: String,
+        field: Option<String>,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+    PermissionDenied {
+        message: String,
+        path: Option<String>,
+    },
+    Io {
+        message: String,
+        path: Option<String>,
+    },
+    /// A file on disk could not be parsed.
+    Parse {
+        message: String,
+        path: Option<String>,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+}
+
+impl AppError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        AppError::NotFound {
+            message: message.into(),
+            path: None,
+        }
+    }
+
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        AppError::Validation {
+            message: message.into(),
+            field: Some(field.to_string()),
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn permission_denied(path: &Path, message: impl Into<String>) -> Self {
+        AppError::PermissionDenied {
+            message: message.into(),
+            path: Some(path.to_string_lossy().to_string()),
+        }
+    }
+
+    pub fn already_exists(path: &Path, message: impl Into<String>) -> Self {
+        AppError::Conflict(Box::new(Conflict {
+            message: message.into(),
+            path: path.to_string_lossy().to_string(),
+            expected: None,
+            actual: None,
+            content: None,
+        }))
+    }
+
+    /// Classifies an I/O failure on `path` by its `ErrorKind`.
+    pub fn io(err: std::io::Error, path: &Path) -> Self {
+        let path_str = Some(path.to_string_lossy().to_string());
+        let message = format!("{}: {}", path.display(), err);
+        match err.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound {
+                message,
+                path: path_str,
+            },
+            std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied {
+                message,
+                path: path_str,
+            },
+            _ => AppError::Io {
+                message,
+                path: path_str,
+            },
+        }
+    }
+
+    /// An I/O failure not tied to a single path.
+    pub fn other(message: impl Into<String>) -> Self {
+        AppError::Io {
+            message: message.into(),
+            path: None,
+        }
+    }
+
+    /// Invalid JSON submitted by the editor.
+    pub fn invalid_json(err: &serde_json::Error) -> Self {
+        AppError::Validation {
+            message: err.to_string(),
+            field: Some("content".to_string()),
+            line: Some(err.line()),
+            column: Some(err.column()),
+        }
+    }
+
+    /// Invalid TOML submitted by the editor; `source` is needed to turn the
+    /// error's byte span into a line and column.
+    pub fn invalid_toml(err: &toml::de::Error, source: &str) -> Self {
+        let (line, column) = err
+            .span()
+            .map(|span| line_column(source, span.start))
+            .unzip();
+        AppError::Validation {
+            message: err.message().to_string(),
+            field: Some("content".to_string()),
+            line,
+            column,
+        }
+    }
+
+    /// A JSON file on disk that failed to parse.
+    pub fn parse_json(err: &serde_json::Error, path: &Path) -> Self {
+        AppError::Parse {
+            message: format!("{}: {}", path.display(), err),
+            path: Some(path.to_string_lossy().to_string()),
+            line: Some(err.line()),
+            column: Some(err.column()),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Conflict(conflict) => &conflict.message,
+            AppError::NotFound { message, .. }
+            | AppError::Validation { message, .. }
+            | AppError::PermissionDenied { message, .. }
+            | AppError::Io { message, .. }
+            | AppError::Parse { message, .. } => message,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// 1-based line and column of a byte offset.
+pub fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let mut end = offset.min(source.len());
+    while !source.is_char_boundary(end) {
+        end -= 1;
+    }
+    let before = &source[..end];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    (line, column)
+}
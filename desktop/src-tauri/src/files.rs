@@ -0,0 +1,120 @@
+//! Crash-safe writes and optimistic concurrency for files the CLIs also edit.
+//!
+//! Readers hand the UI a [`FileVersion`]; writers take it back as `expected`
+//! and refuse to save when the file changed on disk in between.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, AppResult, Conflict};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct FileVersion {
+    /// SHA-256 of the file content, hex encoded.
+    pub hash: String,
+    /// Modification time in ms since the epoch.
+    pub mtime: i64,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+pub fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn version_of(path: &Path, content: &[u8]) -> FileVersion {
+    let mtime = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp_millis())
+        .unwrap_or(0);
+    FileVersion {
+        hash: content_hash(content),
+        mtime,
+    }
+}
+
+/// Current version of `path`, or `None` if it does not exist.
+pub fn file_version(path: &Path) -> Option<FileVersion> {
+    fs::read(path).ok().map(|content| version_of(path, &content))
+}
+
+/// Reads a text file together with its version.
+pub fn read_versioned(path: &Path) -> AppResult<(String, FileVersion)> {
+    let content = fs::read(path).map_err(|e| AppError::io(e, path))?;
+    let version = version_of(path, &content);
+    Ok((String::from_utf8_lossy(&content).to_string(), version))
+}
+
+/// Fails with [`AppError::Conflict`] if `path` no longer matches `expected`.
+/// Content is compared by hash, so a touched-but-unchanged file still saves.
+pub fn check_version(path: &Path, expected: Option<&FileVersion>) -> AppResult<()> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let current = fs::read(path).ok();
+    let actual = current.as_ref().map(|c| version_of(path, c));
+    if actual.as_ref().map(|a| a.hash == expected.hash).unwrap_or(false) {
+        return Ok(());
+    }
+    Err(AppError::Conflict(Box::new(Conflict {
+        message: format!("{} was modified outside the editor", path.display()),
+        path: path.to_string_lossy().to_string(),
+        expected: Some(expected.clone()),
+        actual,
+        content: current.map(|c| String::from_utf8_lossy(&c).to_string()),
+    })))
+}
+
+/// Follows a symlinked target so dotfile managers keep their links intact.
+fn resolve_target(path: &Path) -> PathBuf {
+    if fs::symlink_metadata(path).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        if let Ok(real) = fs::canonicalize(path) {
+            return real;
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Writes via a temp file in the same directory and renames it into place,
+/// so readers see either the old or the new content, never a torn file.
+pub fn write_atomic(path: &Path, content: &[u8]) -> AppResult<FileVersion> {
+    let target = resolve_target(path);
+    let invalid = || AppError::validation("path", format!("Invalid path: {}", path.display()));
+    let dir = target.parent().ok_or_else(invalid)?;
+    let name = target
+        .file_name()
+        .ok_or_else(invalid)?
+        .to_string_lossy()
+        .to_string();
+    let tmp = dir.join(format!(
+        ".{}.tmp-{}-{}",
+        name,
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+    ));
+
+    let result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Ok(meta) = fs::metadata(&target) {
+            fs::set_permissions(&tmp, meta.permissions())?;
+        }
+        fs::rename(&tmp, &target)
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&tmp);
+        return Err(AppError::io(e, &target));
+    }
+    Ok(version_of(&target, content))
+}
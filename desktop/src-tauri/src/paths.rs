@@ -0,0 +1,198 @@
+//! Sandboxed resolution of user-supplied names and relative paths.
+//!
+//! Every command that turns a string from the UI into a filesystem path goes
+//! through [`resolve_within`] (or [`segment`] for single names), so a value
+//! like `../../.ssh/id_rsa` or a symlink planted inside a tool directory can
+//! never reach a file outside that directory.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::{AppError, AppResult};
+
+/// Validates a single path segment such as a session id or project hash.
+pub fn segment(value: &str) -> AppResult<&str> {
+    if value.is_empty()
+        || value == "."
+        || value == ".."
+        || value.contains(['/', '\\', '\0'])
+    {
+        return Err(AppError::validation("name", format!("Invalid name: {:?}", value)));
+    }
+    Ok(value)
+}
+
+/// Joins `relative` onto `root`, rejecting anything that could resolve
+/// outside of it: absolute paths, `..`, drive prefixes, backslashes, NUL
+/// bytes, and symlinks (anywhere along the path) that point elsewhere.
+///
+/// The target itself need not exist yet, so this also guards creates.
+pub fn resolve_within(root: &Path, relative: &str) -> AppResult<PathBuf> {
+    let invalid = || AppError::validation("path", format!("Invalid path: {:?}", relative));
+
+    if relative.is_empty() || relative.contains(['\\', '\0']) {
+        return Err(invalid());
+    }
+
+    let mut joined = root.to_path_buf();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) => joined.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return Err(invalid()),
+        }
+    }
+    if joined == root {
+        return Err(invalid());
+    }
+
+    ensure_contained(root, &joined)?;
+    Ok(joined)
+}
+
+/// Checks that the deepest existing ancestor of `path` (following symlinks)
+/// is still inside `root`. A missing root contains nothing yet, so it passes.
+pub fn ensure_contained(root: &Path, path: &Path) -> AppResult<()> {
+    let Ok(real_root) = fs::canonicalize(root) else {
+        return Ok(());
+    };
+
+    let mut existing = path;
+    loop {
+        if fs::symlink_metadata(existing).is_ok() {
+            break;
+        }
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => return Ok(()),
+        }
+    }
+
+    // A dangling symlink cannot be canonicalised; treat it as an escape.
+    let escape = || {
+        AppError::permission_denied(path, format!("Path escapes {}: {}", root.display(), path.display()))
+    };
+    let real = fs::canonicalize(existing).map_err(|_| escape())?;
+    if !real.starts_with(&real_root) {
+        return Err(escape());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("root");
+        fs::create_dir_all(root.join("commands/nested")).unwrap();
+        fs::write(root.join("commands/ok.md"), "ok").unwrap();
+        fs::write(tmp.path().join("secret"), "secret").unwrap();
+        (tmp, root)
+    }
+
+    #[test]
+    fn allows_plain_and_nested_paths() {
+        let (_tmp, root) = sandbox();
+        assert_eq!(resolve_within(&root, "commands/ok.md").unwrap(), root.join("commands/ok.md"));
+        assert_eq!(
+            resolve_within(&root, "commands/nested/new.md").unwrap(),
+            root.join("commands/nested/new.md")
+        );
+        assert_eq!(resolve_within(&root, "./commands/ok.md").unwrap(), root.join("commands/ok.md"));
+    }
+
+    #[test]
+    fn rejects_parent_dir_traversal() {
+        let (_tmp, root) = sandbox();
+        assert!(resolve_within(&root, "../secret").is_err());
+        assert!(resolve_within(&root, "commands/../../secret").is_err());
+        assert!(resolve_within(&root, "commands/nested/../../../secret").is_err());
+        assert!(resolve_within(&root, "..").is_err());
+    }
+
+    #[test]
+    fn rejects_absolute_paths() {
+        let (tmp, root) = sandbox();
+        assert!(resolve_within(&root, "/etc/passwd").is_err());
+        assert!(resolve_within(&root, &tmp.path().join("secret").to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn rejects_backslashes_and_nul_bytes() {
+        let (_tmp, root) = sandbox();
+        assert!(resolve_within(&root, "..\\secret").is_err());
+        assert!(resolve_within(&root, "commands\\..\\..\\secret").is_err());
+        assert!(resolve_within(&root, "C:\\Windows\\win.ini").is_err());
+        assert!(resolve_within(&root, "ok.md\0.txt").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_and_root_itself() {
+        let (_tmp, root) = sandbox();
+        assert!(resolve_within(&root, "").is_err());
+        assert!(resolve_within(&root, ".").is_err());
+        assert!(resolve_within(&root, "./").is_err());
+    }
+
+    #[test]
+    fn treats_encoded_dots_literally() {
+        let (_tmp, root) = sandbox();
+        assert_eq!(resolve_within(&root, "%2e%2e/secret").unwrap(), root.join("%2e%2e/secret"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinked_file_escape() {
+        let (tmp, root) = sandbox();
+        std::os::unix::fs::symlink(tmp.path().join("secret"), root.join("commands/link.md")).unwrap();
+        assert!(resolve_within(&root, "commands/link.md").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinked_directory_escape() {
+        let (tmp, root) = sandbox();
+        std::os::unix::fs::symlink(tmp.path(), root.join("commands/outside")).unwrap();
+        assert!(resolve_within(&root, "commands/outside/secret").is_err());
+        assert!(resolve_within(&root, "commands/outside/new.md").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_dangling_symlink() {
+        let (tmp, root) = sandbox();
+        std::os::unix::fs::symlink(tmp.path().join("missing"), root.join("commands/dangling.md")).unwrap();
+        assert!(resolve_within(&root, "commands/dangling.md").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn allows_symlinks_that_stay_inside() {
+        let (_tmp, root) = sandbox();
+        std::os::unix::fs::symlink(root.join("commands/ok.md"), root.join("commands/alias.md")).unwrap();
+        assert!(resolve_within(&root, "commands/alias.md").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn allows_symlinked_root() {
+        let (tmp, root) = sandbox();
+        let link = tmp.path().join("root-link");
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+        assert!(resolve_within(&link, "commands/ok.md").is_ok());
+    }
+
+    #[test]
+    fn segment_rejects_separators_and_dots() {
+        assert!(segment("session-2026-01-08T20-46-3ba7254a").is_ok());
+        assert!(segment("").is_err());
+        assert!(segment(".").is_err());
+        assert!(segment("..").is_err());
+        assert!(segment("../x").is_err());
+        assert!(segment("a/b").is_err());
+        assert!(segment("a\\b").is_err());
+        assert!(segment("a\0b").is_err());
+    }
+}
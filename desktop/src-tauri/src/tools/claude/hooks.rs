@@ -0,0 +1,597 @@
+//! Claude Code hooks: shell commands the CLI runs on lifecycle events.
+//!
+//! Settings store them as `hooks.<Event>[group].hooks[index]`, where each
+//! group carries an optional tool-name `matcher` regex. Plugins ship the same
+//! structure in `hooks/hooks.json`; those are listed but read-only.
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use super::settings::{self, SettingsLayer};
+use crate::error::{AppError, AppResult};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Events the CLI fires, and whether their groups take a matcher.
+const EVENTS: &[(&str, bool)] = &[
+    ("PreToolUse", true),
+    ("PostToolUse", true),
+    ("Notification", false),
+    ("UserPromptSubmit", false),
+    ("Stop", false),
+    ("SubagentStop", false),
+    ("PreCompact", true),
+    ("SessionStart", true),
+    ("SessionEnd", false),
+];
+
+/// Captured output is cut off beyond this many bytes per stream.
+const OUTPUT_LIMIT: usize = 64 * 1024;
+
+const DEFAULT_TEST_TIMEOUT_MS: u64 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum HookSource {
+    Settings { layer: SettingsLayer },
+    Plugin { plugin: String },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HookEntry {
+    event: String,
+    matcher: Option<String>,
+    #[serde(rename = "type")]
+    hook_type: String,
+    command: Option<String>,
+    /// Seconds, as stored in settings.
+    timeout: Option<u64>,
+    source: HookSource,
+    path: String,
+    #[serde(rename = "readOnly")]
+    read_only: bool,
+    /// Position within `hooks.<event>`, used to address edits.
+    group: usize,
+    index: usize,
+    issues: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HookEvent {
+    name: String,
+    #[serde(rename = "usesMatcher")]
+    uses_matcher: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HooksView {
+    #[serde(rename = "projectPath")]
+    project_path: Option<String>,
+    hooks: Vec<HookEntry>,
+    events: Vec<HookEvent>,
+}
+
+/// A hook as submitted by the editor.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HookInput {
+    event: String,
+    matcher: Option<String>,
+    command: String,
+    timeout: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HookTestResult {
+    #[serde(rename = "exitCode")]
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+    /// Output was longer than the capture limit.
+    truncated: bool,
+    #[serde(rename = "timedOut")]
+    timed_out: bool,
+    #[serde(rename = "durationMs")]
+    duration_ms: u64,
+    /// Exit code 2 tells the CLI to block the action and show stderr.
+    blocking: bool,
+    /// stdout parsed as JSON, when the hook replied with a decision object.
+    json: Option<serde_json::Value>,
+}
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+fn event_uses_matcher(event: &str) -> Option<bool> {
+    EVENTS.iter().find(|(name, _)| *name == event).map(|(_, m)| *m)
+}
+
+/// Problems with a hook, as messages; empty when it is valid.
+fn hook_issues(event: &str, matcher: Option<&str>, command: Option<&str>) -> Vec<String> {
+    let mut issues = Vec::new();
+    match event_uses_matcher(event) {
+        None => issues.push(format!("Unknown hook event: {}", event)),
+        Some(false) if matcher.is_some_and(|m| !m.is_empty()) => {
+            issues.push(format!("{} hooks ignore the matcher", event))
+        }
+        _ => {}
+    }
+    if let Some(matcher) = matcher.filter(|m| !m.is_empty() && *m != "*") {
+        if let Err(e) = regex::Regex::new(matcher) {
+            issues.push(format!("Invalid matcher regex: {}", e));
+        }
+    }
+    if command.is_some_and(|c| c.trim().is_empty()) {
+        issues.push("Command is empty".to_string());
+    }
+    issues
+}
+
+fn validate(hook: &HookInput) -> AppResult<()> {
+    if event_uses_matcher(&hook.event).is_none() {
+        return Err(AppError::validation("event", format!("Unknown hook event: {}", hook.event)));
+    }
+    if let Some(matcher) = hook.matcher.as_deref().filter(|m| !m.is_empty() && *m != "*") {
+        regex::Regex::new(matcher)
+            .map_err(|e| AppError::validation("matcher", format!("Invalid matcher regex: {}", e)))?;
+    }
+    if hook.command.trim().is_empty() {
+        return Err(AppError::validation("command", "Command must not be empty"));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Listing
+// ============================================================================
+
+fn collect_hooks(
+    hooks: &serde_json::Value,
+    source: HookSource,
+    path: &Path,
+    read_only: bool,
+    out: &mut Vec<HookEntry>,
+) {
+    let Some(events) = hooks.as_object() else {
+        return;
+    };
+    for (event, groups) in events {
+        for (group, entry) in groups.as_array().into_iter().flatten().enumerate() {
+            let matcher = entry.get("matcher").and_then(|m| m.as_str()).map(String::from);
+            for (index, hook) in entry.get("hooks").and_then(|h| h.as_array()).into_iter().flatten().enumerate() {
+                let command = hook.get("command").and_then(|c| c.as_str()).map(String::from);
+                out.push(HookEntry {
+                    event: event.clone(),
+                    issues: hook_issues(event, matcher.as_deref(), command.as_deref()),
+                    matcher: matcher.clone(),
+                    hook_type: hook.get("type").and_then(|t| t.as_str()).unwrap_or("command").to_string(),
+                    command,
+                    timeout: hook.get("timeout").and_then(|t| t.as_u64()),
+                    source: source.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    read_only,
+                    group,
+                    index,
+                });
+            }
+        }
+    }
+}
+
+fn hooks_view(project: Option<&Path>) -> AppResult<HooksView> {
+    let mut hooks = Vec::new();
+
+    for (layer, path) in settings::layer_paths(project)? {
+        if let (_, Some(map)) = settings::read_layer(layer, &path) {
+            if let Some(layer_hooks) = map.get("hooks") {
+                let read_only = layer == SettingsLayer::Managed;
+                collect_hooks(layer_hooks, HookSource::Settings { layer }, &path, read_only, &mut hooks);
+            }
+        }
+    }
+
+    for plugin in super::installed_plugins()? {
+        let path = PathBuf::from(&plugin.install_path).join("hooks").join("hooks.json");
+        let Some(json) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        else {
+            continue;
+        };
+        if let Some(plugin_hooks) = json.get("hooks") {
+            let source = HookSource::Plugin { plugin: plugin.id.clone() };
+            collect_hooks(plugin_hooks, source, &path, true, &mut hooks);
+        }
+    }
+
+    Ok(HooksView {
+        project_path: project.map(|p| p.to_string_lossy().to_string()),
+        hooks,
+        events: EVENTS
+            .iter()
+            .map(|(name, uses_matcher)| HookEvent {
+                name: name.to_string(),
+                uses_matcher: *uses_matcher,
+            })
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Editing
+// ============================================================================
+
+fn hook_value(hook: &HookInput) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "type": "command",
+        "command": hook.command.trim(),
+    });
+    if let Some(timeout) = hook.timeout {
+        value["timeout"] = timeout.into();
+    }
+    value
+}
+
+fn normalized_matcher(hook: &HookInput) -> Option<&str> {
+    hook.matcher.as_deref().map(str::trim).filter(|m| !m.is_empty())
+}
+
+/// Appends `hook` to the group with the same matcher, creating one if needed.
+fn insert_hook(map: &mut serde_json::Map<String, serde_json::Value>, hook: &HookInput, path: &Path) -> AppResult<()> {
+    let not_object = |what: &str| AppError::validation("hooks", format!("{}: {} is not valid", path.display(), what));
+    let hooks = map
+        .entry("hooks")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or_else(|| not_object("\"hooks\""))?;
+    let groups = hooks
+        .entry(hook.event.clone())
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| not_object(&format!("hooks.{}", hook.event)))?;
+
+    let matcher = normalized_matcher(hook);
+    let existing = groups
+        .iter_mut()
+        .find(|g| g.get("matcher").and_then(|m| m.as_str()).filter(|m| !m.is_empty()) == matcher);
+    match existing.and_then(|g| g.get_mut("hooks")).and_then(|h| h.as_array_mut()) {
+        Some(entries) => entries.push(hook_value(hook)),
+        None => {
+            let mut group = serde_json::Map::new();
+            if let Some(matcher) = matcher {
+                group.insert("matcher".to_string(), matcher.into());
+            }
+            group.insert("hooks".to_string(), serde_json::Value::Array(vec![hook_value(hook)]));
+            groups.push(serde_json::Value::Object(group));
+        }
+    }
+    Ok(())
+}
+
+/// Removes `hooks.<event>[group].hooks[index]`, pruning empty containers.
+fn take_hook(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    event: &str,
+    group: usize,
+    index: usize,
+) -> AppResult<serde_json::Value> {
+    let missing = || AppError::not_found(format!("No {} hook at {}/{}", event, group, index));
+    let hooks = map.get_mut("hooks").and_then(|h| h.as_object_mut()).ok_or_else(missing)?;
+    let groups = hooks.get_mut(event).and_then(|g| g.as_array_mut()).ok_or_else(missing)?;
+    let entries = groups
+        .get_mut(group)
+        .and_then(|g| g.get_mut("hooks"))
+        .and_then(|h| h.as_array_mut())
+        .ok_or_else(missing)?;
+    if index >= entries.len() {
+        return Err(missing());
+    }
+    let removed = entries.remove(index);
+
+    if entries.is_empty() {
+        groups.remove(group);
+    }
+    if groups.is_empty() {
+        hooks.remove(event);
+    }
+    if hooks.is_empty() {
+        map.remove("hooks");
+    }
+    Ok(removed)
+}
+
+fn resolve_project(project_path: Option<String>) -> AppResult<Option<PathBuf>> {
+    project_path.as_deref().map(settings::project_dir).transpose()
+}
+
+// ============================================================================
+// Dry run
+// ============================================================================
+
+/// A representative payload for `event`, matching what the CLI sends on stdin.
+fn sample_payload(event: &str, project: Option<&Path>) -> serde_json::Value {
+    let cwd = project
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "/tmp/project".to_string());
+    let mut payload = serde_json::json!({
+        "session_id": "00000000-0000-0000-0000-000000000000",
+        "transcript_path": "/tmp/transcript.jsonl",
+        "cwd": cwd,
+        "hook_event_name": event,
+    });
+    let extra = match event {
+        "PreToolUse" => serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "npm test", "description": "Run tests" },
+        }),
+        "PostToolUse" => serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "npm test", "description": "Run tests" },
+            "tool_response": { "stdout": "ok", "stderr": "", "interrupted": false },
+        }),
+        "Notification" => serde_json::json!({ "message": "Claude needs your permission to use Bash" }),
+        "UserPromptSubmit" => serde_json::json!({ "prompt": "Write a function to add two numbers" }),
+        "Stop" | "SubagentStop" => serde_json::json!({ "stop_hook_active": false }),
+        "PreCompact" => serde_json::json!({ "trigger": "manual", "custom_instructions": "" }),
+        "SessionStart" => serde_json::json!({ "source": "startup" }),
+        "SessionEnd" => serde_json::json!({ "reason": "exit" }),
+        _ => serde_json::json!({}),
+    };
+    if let (Some(payload), Some(extra)) = (payload.as_object_mut(), extra.as_object()) {
+        payload.extend(extra.clone());
+    }
+    payload
+}
+
+fn shell(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        use std::os::unix::process::CommandExt;
+        let mut cmd = Command::new("sh");
+        // Own process group, so a timeout also kills anything the hook spawned.
+        cmd.arg("-c").arg(command).process_group(0);
+        cmd
+    }
+}
+
+/// Kills the hook and its descendants; otherwise a grandchild holding the
+/// output pipes open would keep the capture threads waiting.
+fn kill_tree(child: &mut std::process::Child) {
+    let pid = child.id().to_string();
+    let status = if cfg!(windows) {
+        Command::new("taskkill").args(["/T", "/F", "/PID", &pid]).status()
+    } else {
+        Command::new("kill").args(["-KILL", &format!("-{}", pid)]).status()
+    };
+    if !status.is_ok_and(|s| s.success()) {
+        let _ = child.kill();
+    }
+    let _ = child.wait();
+}
+
+/// Reads a stream to its end on a thread, keeping at most [`OUTPUT_LIMIT`]
+/// bytes so a chatty hook cannot exhaust memory.
+fn capture(mut stream: impl Read + Send + 'static) -> std::thread::JoinHandle<(Vec<u8>, bool)> {
+    std::thread::spawn(move || {
+        let mut kept = Vec::new();
+        let mut truncated = false;
+        let mut buf = [0u8; 8192];
+        while let Ok(n) = stream.read(&mut buf) {
+            if n == 0 {
+                break;
+            }
+            let room = OUTPUT_LIMIT.saturating_sub(kept.len());
+            kept.extend_from_slice(&buf[..n.min(room)]);
+            truncated |= n > room;
+        }
+        (kept, truncated)
+    })
+}
+
+/// Runs `command` the way the CLI would, but in a scratch directory with a
+/// minimal environment, a timeout and capped output.
+fn run_hook(command: &str, payload: &serde_json::Value, project: Option<&Path>, timeout: Duration) -> AppResult<HookTestResult> {
+    let scratch = std::env::temp_dir().join(format!(
+        "ai-command-center-hook-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+    ));
+    std::fs::create_dir_all(&scratch).map_err(|e| AppError::io(e, &scratch))?;
+
+    let mut cmd = shell(command);
+    cmd.current_dir(&scratch)
+        .env_clear()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    for var in ["PATH", "HOME", "USER", "LANG", "SystemRoot", "COMSPEC", "PATHEXT"] {
+        if let Ok(value) = std::env::var(var) {
+            cmd.env(var, value);
+        }
+    }
+    cmd.env("TMPDIR", &scratch);
+    if let Some(project) = project {
+        cmd.env("CLAUDE_PROJECT_DIR", project);
+    }
+
+    let started = Instant::now();
+    let result = (|| -> std::io::Result<HookTestResult> {
+        let mut child = cmd.spawn()?;
+        let stdout = capture(child.stdout.take().unwrap());
+        let stderr = capture(child.stderr.take().unwrap());
+
+        let input = serde_json::to_vec(payload).unwrap_or_default();
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = std::thread::spawn(move || {
+            // The hook may exit without reading stdin; that is not an error.
+            let _ = stdin.write_all(&input);
+        });
+
+        let mut timed_out = false;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            }
+            if started.elapsed() >= timeout {
+                timed_out = true;
+                kill_tree(&mut child);
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let _ = writer.join();
+
+        let (out, out_truncated) = stdout.join().unwrap_or_default();
+        let (err, err_truncated) = stderr.join().unwrap_or_default();
+        let stdout = String::from_utf8_lossy(&out).to_string();
+        let exit_code = status.and_then(|s| s.code());
+        Ok(HookTestResult {
+            exit_code,
+            json: serde_json::from_str(stdout.trim()).ok().filter(|v: &serde_json::Value| v.is_object()),
+            stdout,
+            stderr: String::from_utf8_lossy(&err).to_string(),
+            truncated: out_truncated || err_truncated,
+            timed_out,
+            duration_ms: started.elapsed().as_millis() as u64,
+            blocking: exit_code == Some(2),
+        })
+    })();
+
+    let _ = std::fs::remove_dir_all(&scratch);
+    result.map_err(|e| AppError::other(format!("Failed to run hook: {}", e)))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Hooks from every settings layer and installed plugin.
+#[tauri::command]
+pub fn claude_get_hooks(project_path: Option<String>) -> AppResult<HooksView> {
+    let project = resolve_project(project_path)?;
+    hooks_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_validate_hook(hook: HookInput) -> AppResult<()> {
+    validate(&hook)
+}
+
+#[tauri::command]
+pub fn claude_add_hook(project_path: Option<String>, scope: SettingsLayer, hook: HookInput) -> AppResult<HooksView> {
+    let project = resolve_project(project_path)?;
+    validate(&hook)?;
+    let path = settings::editable_layer_path(scope, project.as_deref())?;
+    settings::edit_layer(&path, |map| insert_hook(map, &hook, &path))?;
+    hooks_view(project.as_deref())
+}
+
+/// Replaces the hook at `event`/`group`/`index`. A changed event or matcher
+/// moves it to the matching group.
+#[tauri::command]
+pub fn claude_update_hook(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    event: String,
+    group: usize,
+    index: usize,
+    hook: HookInput,
+) -> AppResult<HooksView> {
+    let project = resolve_project(project_path)?;
+    validate(&hook)?;
+    let path = settings::editable_layer_path(scope, project.as_deref())?;
+    settings::edit_layer(&path, |map| {
+        let current_matcher = map
+            .get("hooks")
+            .and_then(|h| h[event.as_str()][group]["matcher"].as_str())
+            .filter(|m| !m.is_empty());
+        let same_group = event == hook.event && current_matcher == normalized_matcher(&hook);
+        if same_group {
+            let slot = map
+                .get_mut("hooks")
+                .and_then(|h| h.get_mut(&event))
+                .and_then(|g| g.get_mut(group))
+                .and_then(|g| g.get_mut("hooks"))
+                .and_then(|h| h.get_mut(index))
+                .ok_or_else(|| AppError::not_found(format!("No {} hook at {}/{}", event, group, index)))?;
+            // Keep keys the editor does not know about (e.g. newer fields).
+            match slot.as_object_mut() {
+                Some(existing) => {
+                    if let Some(new) = hook_value(&hook).as_object() {
+                        existing.extend(new.clone());
+                    }
+                    if hook.timeout.is_none() {
+                        existing.remove("timeout");
+                    }
+                }
+                None => *slot = hook_value(&hook),
+            }
+            return Ok(());
+        }
+        take_hook(map, &event, group, index)?;
+        insert_hook(map, &hook, &path)
+    })?;
+    hooks_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_remove_hook(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    event: String,
+    group: usize,
+    index: usize,
+) -> AppResult<HooksView> {
+    let project = resolve_project(project_path)?;
+    let path = settings::editable_layer_path(scope, project.as_deref())?;
+    settings::edit_layer(&path, |map| take_hook(map, &event, group, index).map(|_| ()))?;
+    hooks_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_hook_sample_payload(event: String, project_path: Option<String>) -> AppResult<serde_json::Value> {
+    let project = resolve_project(project_path)?;
+    Ok(sample_payload(&event, project.as_deref()))
+}
+
+/// Runs a hook command against `payload` (or a sample payload for `event`)
+/// and reports what the CLI would see.
+#[tauri::command]
+pub async fn claude_test_hook(
+    command: String,
+    event: String,
+    payload: Option<serde_json::Value>,
+    project_path: Option<String>,
+    timeout_ms: Option<u64>,
+) -> AppResult<HookTestResult> {
+    if command.trim().is_empty() {
+        return Err(AppError::validation("command", "Command must not be empty"));
+    }
+    let project = resolve_project(project_path)?;
+    let payload = payload.unwrap_or_else(|| sample_payload(&event, project.as_deref()));
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_TEST_TIMEOUT_MS));
+
+    tauri::async_runtime::spawn_blocking(move || run_hook(&command, &payload, project.as_deref(), timeout))
+        .await
+        .map_err(|e| AppError::other(e.to_string()))?
+}
+#[cfg(test)]
+mod scratch {
+    use super::*;
+    #[test]
+    fn t() {
+        let r = run_hook("sleep 5", &serde_json::json!({}), None, Duration::from_millis(200)).unwrap();
+        println!("{:?} {} {}", r.exit_code, r.timed_out, r.duration_ms);
+    }
+}
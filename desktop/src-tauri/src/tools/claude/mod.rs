@@ -0,0 +1,195 @@
+pub mod hooks;
+pub mod permissions;
+pub mod settings;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ConfigFormat, HistoryEntry, SettingsFile, SkillLayout, ToolAdapter};
+use crate::error::{AppError, AppResult};
+use crate::files::{self, FileVersion};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+pub struct ClaudeSettings {
+    settings: serde_json::Value,
+    local: serde_json::Value,
+    /// Version of each file by scope, for conflict-checked saves.
+    versions: HashMap<String, FileVersion>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ClaudePlugin {
+    id: String,
+    name: String,
+    marketplace: String,
+    version: String,
+    #[serde(rename = "installPath")]
+    install_path: String,
+    #[serde(rename = "installedAt")]
+    installed_at: String,
+    #[serde(rename = "lastUpdated")]
+    last_updated: String,
+    scope: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PluginsResult {
+    plugins: Vec<ClaudePlugin>,
+}
+
+// ============================================================================
+// Adapter
+// ============================================================================
+
+pub struct ClaudeAdapter;
+
+impl ToolAdapter for ClaudeAdapter {
+    fn id(&self) -> &'static str {
+        "claude"
+    }
+
+    fn name(&self) -> &'static str {
+        "Claude Code"
+    }
+
+    fn default_dir(&self) -> &'static str {
+        ".claude"
+    }
+
+    fn env_var(&self) -> Option<&'static str> {
+        Some("CLAUDE_CONFIG_DIR")
+    }
+
+    fn settings_files(&self, root: &Path) -> Vec<SettingsFile> {
+        vec![
+            SettingsFile {
+                scope: "settings",
+                path: root.join("settings.json"),
+                format: ConfigFormat::Json,
+            },
+            SettingsFile {
+                scope: "local",
+                path: root.join("settings.local.json"),
+                format: ConfigFormat::Json,
+            },
+        ]
+    }
+
+    fn skills_dir(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("skills"))
+    }
+
+    fn skill_layout(&self) -> SkillLayout {
+        SkillLayout::MarkdownFile
+    }
+
+    fn prompts_dir(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("commands"))
+    }
+
+    fn prompts_nested(&self) -> bool {
+        true
+    }
+
+    fn history_file(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("history.jsonl"))
+    }
+
+    fn parse_history_entry(&self, id: usize, v: &serde_json::Value) -> Option<HistoryEntry> {
+        Some(HistoryEntry {
+            id,
+            text: v["display"].as_str().unwrap_or("").to_string(),
+            timestamp: v["timestamp"].as_i64().unwrap_or(0),
+            session_id: v["sessionId"].as_str().unwrap_or("").to_string(),
+            project: v["project"].as_str().map(|s| s.to_string()),
+        })
+    }
+
+    fn get_settings(&self) -> AppResult<serde_json::Value> {
+        let read = |path: PathBuf| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or(serde_json::json!({}))
+        };
+
+        let root = self.root_dir()?;
+        let settings = read(root.join("settings.json"));
+        let local = read(root.join("settings.local.json"));
+        let versions = self
+            .settings_files(&root)
+            .into_iter()
+            .filter_map(|f| files::file_version(&f.path).map(|v| (f.scope.to_string(), v)))
+            .collect();
+
+        serde_json::to_value(ClaudeSettings {
+            settings,
+            local,
+            versions,
+        })
+        .map_err(|e| AppError::other(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Claude-only Commands
+// ============================================================================
+
+/// Plugins recorded in `plugins/installed_plugins.json`, sorted by name.
+pub fn installed_plugins() -> AppResult<Vec<ClaudePlugin>> {
+    let plugins_path = ClaudeAdapter.root_dir()?.join("plugins").join("installed_plugins.json");
+
+    let content = match fs::read_to_string(&plugins_path) {
+        Ok(c) => c,
+        Err(_) => return Ok(vec![]),
+    };
+
+    let json: serde_json::Value = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(_) => return Ok(vec![]),
+    };
+
+    let mut plugins = Vec::new();
+
+    // Parse the nested structure: { "plugins": { "id@marketplace": [{ ... }] } }
+    if let Some(plugins_obj) = json.get("plugins").and_then(|p| p.as_object()) {
+        for (key, installations) in plugins_obj {
+            // key is like "claude-hud@claude-hud" or "github@claude-plugins-official"
+            let parts: Vec<&str> = key.split('@').collect();
+            let name = parts.first().unwrap_or(&"").to_string();
+            let marketplace = parts.get(1).unwrap_or(&"").to_string();
+
+            // Get the first (most recent) installation
+            if let Some(install) = installations.as_array().and_then(|arr| arr.first()) {
+                plugins.push(ClaudePlugin {
+                    id: key.clone(),
+                    name,
+                    marketplace,
+                    version: install.get("version").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    install_path: install.get("installPath").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    installed_at: install.get("installedAt").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    last_updated: install.get("lastUpdated").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    scope: install.get("scope").and_then(|v| v.as_str()).unwrap_or("user").to_string(),
+                });
+            }
+        }
+    }
+
+    // Sort by name
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(plugins)
+}
+
+#[tauri::command]
+pub fn claude_get_plugins() -> AppResult<PluginsResult> {
+    Ok(PluginsResult {
+        plugins: installed_plugins()?,
+    })
+}
@@ -0,0 +1,557 @@
+//! Structured editing of the `permissions` block in Claude settings.
+//!
+//! Rules look like `Tool` or `Tool(specifier)`, e.g. `Bash(npm run test:*)`
+//! or `Read(./src/**)`. Claude concatenates the rules of every layer and
+//! evaluates deny, then ask, then allow, so a rule can be made redundant by a
+//! broader one in the same list or overruled by one in a stronger list.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::settings::{self, SettingsLayer};
+use crate::error::{AppError, AppResult};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionList {
+    Allow,
+    Deny,
+    Ask,
+}
+
+impl PermissionList {
+    fn key(self) -> &'static str {
+        match self {
+            PermissionList::Allow => "allow",
+            PermissionList::Deny => "deny",
+            PermissionList::Ask => "ask",
+        }
+    }
+
+    /// Evaluation order: deny wins over ask, ask over allow.
+    fn strength(self) -> u8 {
+        match self {
+            PermissionList::Deny => 2,
+            PermissionList::Ask => 1,
+            PermissionList::Allow => 0,
+        }
+    }
+}
+
+const LISTS: [PermissionList; 3] = [PermissionList::Deny, PermissionList::Ask, PermissionList::Allow];
+
+const DEFAULT_MODES: [&str; 4] = ["default", "acceptEdits", "plan", "bypassPermissions"];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ParsedRule {
+    pub tool: String,
+    /// Text inside the parentheses; `None` matches every use of the tool.
+    pub specifier: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleIssueKind {
+    /// The same rule appears earlier in the same list.
+    Duplicate,
+    /// A broader rule makes this one redundant or overrules it.
+    Shadowed,
+    /// The rule does not follow `Tool` / `Tool(specifier)` syntax.
+    Invalid,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RuleRef {
+    rule: String,
+    list: PermissionList,
+    layer: SettingsLayer,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RuleIssue {
+    kind: RuleIssueKind,
+    message: String,
+    by: Option<RuleRef>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PermissionRule {
+    rule: String,
+    list: PermissionList,
+    layer: SettingsLayer,
+    tool: Option<String>,
+    specifier: Option<String>,
+    issues: Vec<RuleIssue>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LayerValue {
+    value: String,
+    layer: SettingsLayer,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PermissionsView {
+    #[serde(rename = "projectPath")]
+    project_path: Option<String>,
+    /// Rules of every layer, lowest precedence layer first, then deny, ask,
+    /// allow within a layer.
+    rules: Vec<PermissionRule>,
+    #[serde(rename = "additionalDirectories")]
+    additional_directories: Vec<LayerValue>,
+    /// The effective mode: the value from the highest layer that sets one.
+    #[serde(rename = "defaultMode")]
+    default_mode: Option<LayerValue>,
+}
+
+// ============================================================================
+// Rule syntax
+// ============================================================================
+
+/// Splits `Tool(specifier)` into its parts.
+pub fn parse_rule(rule: &str) -> AppResult<ParsedRule> {
+    let rule = rule.trim();
+    let invalid = |why: &str| AppError::validation("rule", format!("Invalid permission rule {:?}: {}", rule, why));
+
+    let (tool, specifier) = match rule.find('(') {
+        Some(open) => {
+            let inner = rule[open + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| invalid("missing closing parenthesis"))?;
+            if inner.trim().is_empty() {
+                return Err(invalid("empty specifier"));
+            }
+            (&rule[..open], Some(inner.to_string()))
+        }
+        None if rule.contains(')') => return Err(invalid("unbalanced parenthesis")),
+        None => (rule, None),
+    };
+
+    if tool.is_empty() {
+        return Err(invalid("missing tool name"));
+    }
+    if !tool.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '*')) {
+        return Err(invalid("tool names may only contain letters, digits, '_', '-' and '*'"));
+    }
+    Ok(ParsedRule {
+        tool: tool.to_string(),
+        specifier,
+    })
+}
+
+/// `*` matches any run of characters (`**` behaves the same).
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, rest) = parts.split_first().unwrap();
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let (last, middle) = rest.split_last().unwrap();
+    for part in middle {
+        match remaining.find(part) {
+            Some(at) => remaining = &remaining[at + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last)
+}
+
+fn tool_covers(broad: &str, narrow: &str) -> bool {
+    if broad == narrow {
+        return true;
+    }
+    // `mcp__server` and `mcp__server__*` cover every tool of that server.
+    if broad.starts_with("mcp__") {
+        let server = broad.trim_end_matches('*').trim_end_matches("__");
+        return narrow.starts_with(&format!("{}__", server));
+    }
+    false
+}
+
+/// Whether `broad` matches everything `narrow` matches.
+fn covers(broad: &ParsedRule, narrow: &ParsedRule) -> bool {
+    if !tool_covers(&broad.tool, &narrow.tool) {
+        return false;
+    }
+    if broad.tool != narrow.tool {
+        // A server-wide MCP rule covers any specifier of its tools.
+        return broad.specifier.is_none();
+    }
+    match (&broad.specifier, &narrow.specifier) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(b), Some(n)) => {
+            // Bash's `prefix:*` matches any command starting with `prefix`.
+            if let Some(prefix) = b.strip_suffix(":*") {
+                let n = n.strip_suffix(":*").unwrap_or(n);
+                return n.starts_with(prefix);
+            }
+            wildcard_match(b, n)
+        }
+    }
+}
+
+// ============================================================================
+// Analysis
+// ============================================================================
+
+fn string_array(value: Option<&serde_json::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+fn analyse(rules: &mut [PermissionRule]) {
+    let parsed: Vec<Option<ParsedRule>> = rules.iter().map(|r| parse_rule(&r.rule).ok()).collect();
+    let refs: Vec<RuleRef> = rules
+        .iter()
+        .map(|r| RuleRef {
+            rule: r.rule.clone(),
+            list: r.list,
+            layer: r.layer,
+        })
+        .collect();
+
+    for (i, rule) in rules.iter_mut().enumerate() {
+        let Some(this) = &parsed[i] else {
+            let message = parse_rule(&rule.rule).err().map(|e| e.to_string()).unwrap_or_default();
+            rule.issues.push(RuleIssue {
+                kind: RuleIssueKind::Invalid,
+                message,
+                by: None,
+            });
+            continue;
+        };
+
+        if let Some(first) = (0..i).find(|&j| refs[j].list == rule.list && parsed[j].as_ref() == Some(this)) {
+            rule.issues.push(RuleIssue {
+                kind: RuleIssueKind::Duplicate,
+                message: format!("Also listed in {} settings", layer_name(refs[first].layer)),
+                by: Some(refs[first].clone()),
+            });
+            continue;
+        }
+
+        let shadow = (0..refs.len()).find(|&j| {
+            let Some(other) = &parsed[j] else {
+                return false;
+            };
+            if j == i || !covers(other, this) {
+                return false;
+            }
+            let stronger = refs[j].list.strength() > rule.list.strength();
+            let broader_in_list = refs[j].list == rule.list && other != this;
+            stronger || broader_in_list
+        });
+        if let Some(j) = shadow {
+            let by = &refs[j];
+            let message = if by.list == rule.list {
+                format!("Already covered by {} in {} settings", by.rule, layer_name(by.layer))
+            } else {
+                format!(
+                    "Never applies: {} in {} ({} settings) takes precedence",
+                    by.rule,
+                    by.list.key(),
+                    layer_name(by.layer)
+                )
+            };
+            rule.issues.push(RuleIssue {
+                kind: RuleIssueKind::Shadowed,
+                message,
+                by: Some(by.clone()),
+            });
+        }
+    }
+}
+
+fn layer_name(layer: SettingsLayer) -> &'static str {
+    match layer {
+        SettingsLayer::User => "user",
+        SettingsLayer::Project => "project",
+        SettingsLayer::Local => "local",
+        SettingsLayer::Managed => "managed",
+    }
+}
+
+fn permissions_view(project: Option<&Path>) -> AppResult<PermissionsView> {
+    let mut rules = Vec::new();
+    let mut additional_directories = Vec::new();
+    let mut default_mode = None;
+
+    for (layer, path) in settings::layer_paths(project)? {
+        let (_, Some(map)) = settings::read_layer(layer, &path) else {
+            continue;
+        };
+        let Some(permissions) = map.get("permissions") else {
+            continue;
+        };
+        for list in LISTS {
+            for rule in string_array(permissions.get(list.key())) {
+                let parsed = parse_rule(&rule).ok();
+                rules.push(PermissionRule {
+                    tool: parsed.as_ref().map(|p| p.tool.clone()),
+                    specifier: parsed.and_then(|p| p.specifier),
+                    rule,
+                    list,
+                    layer,
+                    issues: Vec::new(),
+                });
+            }
+        }
+        for dir in string_array(permissions.get("additionalDirectories")) {
+            additional_directories.push(LayerValue { value: dir, layer });
+        }
+        if let Some(mode) = permissions.get("defaultMode").and_then(|v| v.as_str()) {
+            default_mode = Some(LayerValue {
+                value: mode.to_string(),
+                layer,
+            });
+        }
+    }
+
+    analyse(&mut rules);
+    Ok(PermissionsView {
+        project_path: project.map(|p| p.to_string_lossy().to_string()),
+        rules,
+        additional_directories,
+        default_mode,
+    })
+}
+
+// ============================================================================
+// Editing
+// ============================================================================
+
+/// Applies `edit` to the `permissions` object of one layer, dropping it
+/// again if the edit leaves it empty.
+fn edit_permissions<T>(
+    project: Option<&Path>,
+    scope: SettingsLayer,
+    edit: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>, &Path) -> AppResult<T>,
+) -> AppResult<T> {
+    let path = settings::editable_layer_path(scope, project)?;
+    settings::edit_layer(&path, |map| {
+        let permissions = map
+            .entry("permissions")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        let Some(permissions) = permissions.as_object_mut() else {
+            return Err(AppError::validation(
+                "permissions",
+                format!("{}: \"permissions\" is not an object", path.display()),
+            ));
+        };
+        let result = edit(permissions, &path)?;
+        if permissions.is_empty() {
+            map.remove("permissions");
+        }
+        Ok(result)
+    })
+}
+
+/// Mutable string array under `key`, created if missing.
+fn array_mut<'a>(
+    permissions: &'a mut serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    path: &Path,
+) -> AppResult<&'a mut Vec<serde_json::Value>> {
+    permissions
+        .entry(key)
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| {
+            AppError::validation(
+                "permissions",
+                format!("{}: permissions.{} is not an array", path.display(), key),
+            )
+        })
+}
+
+fn drop_if_empty(permissions: &mut serde_json::Map<String, serde_json::Value>, key: &str) {
+    if permissions.get(key).and_then(|v| v.as_array()).is_some_and(|a| a.is_empty()) {
+        permissions.remove(key);
+    }
+}
+
+fn insert_rule(
+    permissions: &mut serde_json::Map<String, serde_json::Value>,
+    list: PermissionList,
+    rule: &str,
+    path: &Path,
+) -> AppResult<()> {
+    let rules = array_mut(permissions, list.key(), path)?;
+    if rules.iter().any(|r| r.as_str() == Some(rule)) {
+        return Err(AppError::already_exists(
+            path,
+            format!("{} is already in {}", rule, list.key()),
+        ));
+    }
+    rules.push(serde_json::Value::String(rule.to_string()));
+    Ok(())
+}
+
+fn take_rule(
+    permissions: &mut serde_json::Map<String, serde_json::Value>,
+    list: PermissionList,
+    rule: &str,
+    path: &Path,
+) -> AppResult<()> {
+    let rules = array_mut(permissions, list.key(), path)?;
+    let before = rules.len();
+    rules.retain(|r| r.as_str() != Some(rule));
+    let removed = rules.len() != before;
+    drop_if_empty(permissions, list.key());
+    if !removed {
+        return Err(AppError::NotFound {
+            message: format!("{} is not in {}", rule, list.key()),
+            path: Some(path.to_string_lossy().to_string()),
+        });
+    }
+    Ok(())
+}
+
+fn resolve_project(project_path: Option<String>) -> AppResult<Option<std::path::PathBuf>> {
+    project_path.as_deref().map(settings::project_dir).transpose()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Every permission rule that applies in `project_path`, with duplicates and
+/// shadowed rules flagged.
+#[tauri::command]
+pub fn claude_get_permissions(project_path: Option<String>) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    permissions_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_parse_permission_rule(rule: String) -> AppResult<ParsedRule> {
+    parse_rule(&rule)
+}
+
+#[tauri::command]
+pub fn claude_add_permission(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    list: PermissionList,
+    rule: String,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    let rule = rule.trim();
+    parse_rule(rule)?;
+    edit_permissions(project.as_deref(), scope, |permissions, path| {
+        insert_rule(permissions, list, rule, path)
+    })?;
+    permissions_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_remove_permission(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    list: PermissionList,
+    rule: String,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    edit_permissions(project.as_deref(), scope, |permissions, path| {
+        take_rule(permissions, list, &rule, path)
+    })?;
+    permissions_view(project.as_deref())
+}
+
+/// Moves a rule between allow, deny and ask within one scope.
+#[tauri::command]
+pub fn claude_move_permission(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    rule: String,
+    from: PermissionList,
+    to: PermissionList,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    if from != to {
+        edit_permissions(project.as_deref(), scope, |permissions, path| {
+            take_rule(permissions, from, &rule, path)?;
+            insert_rule(permissions, to, &rule, path)
+        })?;
+    }
+    permissions_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_add_directory(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    directory: String,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    let directory = directory.trim();
+    if directory.is_empty() {
+        return Err(AppError::validation("directory", "Directory must not be empty"));
+    }
+    edit_permissions(project.as_deref(), scope, |permissions, path| {
+        let dirs = array_mut(permissions, "additionalDirectories", path)?;
+        if dirs.iter().any(|d| d.as_str() == Some(directory)) {
+            return Err(AppError::already_exists(path, format!("{} is already listed", directory)));
+        }
+        dirs.push(serde_json::Value::String(directory.to_string()));
+        Ok(())
+    })?;
+    permissions_view(project.as_deref())
+}
+
+#[tauri::command]
+pub fn claude_remove_directory(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    directory: String,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    edit_permissions(project.as_deref(), scope, |permissions, path| {
+        let dirs = array_mut(permissions, "additionalDirectories", path)?;
+        let before = dirs.len();
+        dirs.retain(|d| d.as_str() != Some(directory.as_str()));
+        let removed = dirs.len() != before;
+        drop_if_empty(permissions, "additionalDirectories");
+        if !removed {
+            return Err(AppError::not_found(format!("{} is not listed", directory)));
+        }
+        Ok(())
+    })?;
+    permissions_view(project.as_deref())
+}
+
+/// Sets `permissions.defaultMode` for one scope, or clears it with `None`.
+#[tauri::command]
+pub fn claude_set_default_mode(
+    project_path: Option<String>,
+    scope: SettingsLayer,
+    mode: Option<String>,
+) -> AppResult<PermissionsView> {
+    let project = resolve_project(project_path)?;
+    if let Some(mode) = &mode {
+        if !DEFAULT_MODES.contains(&mode.as_str()) {
+            return Err(AppError::validation(
+                "mode",
+                format!("Unknown mode {:?}; expected one of {}", mode, DEFAULT_MODES.join(", ")),
+            ));
+        }
+    }
+    edit_permissions(project.as_deref(), scope, |permissions, _| {
+        match mode {
+            Some(mode) => permissions.insert("defaultMode".to_string(), serde_json::Value::String(mode)),
+            None => permissions.remove("defaultMode"),
+        };
+        Ok(())
+    })?;
+    permissions_view(project.as_deref())
+}
@@ -0,0 +1,353 @@
+//! Claude Code's settings hierarchy, merged the way the CLI merges it.
+//!
+//! From lowest to highest precedence: user (`~/.claude/settings.json`),
+//! project (`.claude/settings.json`), local (`.claude/settings.local.json`)
+//! and enterprise managed settings, which nothing can override.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::ClaudeAdapter;
+use crate::backups::{create_backup, Action};
+use crate::error::{AppError, AppResult};
+use crate::files::{self, FileVersion};
+use crate::tools::ToolAdapter;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsLayer {
+    User,
+    Project,
+    Local,
+    Managed,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LayerInfo {
+    layer: SettingsLayer,
+    path: String,
+    exists: bool,
+    version: Option<FileVersion>,
+    /// Set when the file exists but is not valid JSON; the layer is skipped.
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct KeyOrigin {
+    /// Dotted path for display, e.g. `permissions.defaultMode`.
+    key: String,
+    path: Vec<String>,
+    /// Layer whose value wins.
+    layer: SettingsLayer,
+    /// Lower layers that also set this key and were replaced.
+    overrides: Vec<SettingsLayer>,
+    /// For arrays the CLI concatenates (permission rules, hooks), every
+    /// layer that contributed entries, lowest first.
+    #[serde(rename = "mergedFrom")]
+    merged_from: Vec<SettingsLayer>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct EffectiveSettings {
+    #[serde(rename = "projectPath")]
+    project_path: Option<String>,
+    settings: serde_json::Value,
+    /// Every layer in precedence order, lowest first, including missing files.
+    layers: Vec<LayerInfo>,
+    keys: Vec<KeyOrigin>,
+}
+
+// ============================================================================
+// Layers
+// ============================================================================
+
+fn managed_settings_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        Some(PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json"))
+    } else if cfg!(windows) {
+        Some(PathBuf::from(r"C:\Program Files\ClaudeCode\managed-settings.json"))
+    } else if cfg!(unix) {
+        Some(PathBuf::from("/etc/claude-code/managed-settings.json"))
+    } else {
+        None
+    }
+}
+
+/// Settings files that apply to `project`, lowest precedence first.
+pub fn layer_paths(project: Option<&Path>) -> AppResult<Vec<(SettingsLayer, PathBuf)>> {
+    let mut layers = vec![(SettingsLayer::User, ClaudeAdapter.root_dir()?.join("settings.json"))];
+    if let Some(project) = project {
+        let dir = project.join(".claude");
+        layers.push((SettingsLayer::Project, dir.join("settings.json")));
+        layers.push((SettingsLayer::Local, dir.join("settings.local.json")));
+    }
+    if let Some(managed) = managed_settings_path() {
+        layers.push((SettingsLayer::Managed, managed));
+    }
+    Ok(layers)
+}
+
+/// Validates a project path from the UI: it must be an existing directory.
+pub fn project_dir(project_path: &str) -> AppResult<PathBuf> {
+    let path = PathBuf::from(project_path.trim());
+    if !path.is_absolute() {
+        return Err(AppError::validation(
+            "projectPath",
+            format!("Project path must be absolute: {}", project_path),
+        ));
+    }
+    if !path.is_dir() {
+        return Err(AppError::NotFound {
+            message: format!("Project directory not found: {}", path.display()),
+            path: Some(path.to_string_lossy().to_string()),
+        });
+    }
+    Ok(path)
+}
+
+pub fn read_layer(layer: SettingsLayer, path: &Path) -> (LayerInfo, Option<serde_json::Map<String, serde_json::Value>>) {
+    let mut info = LayerInfo {
+        layer,
+        path: path.to_string_lossy().to_string(),
+        exists: false,
+        version: None,
+        error: None,
+    };
+    let Ok((content, version)) = files::read_versioned(path) else {
+        return (info, None);
+    };
+    info.exists = true;
+    info.version = Some(version);
+
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(serde_json::Value::Object(map)) => (info, Some(map)),
+        Ok(_) => {
+            info.error = Some("Settings file is not a JSON object".to_string());
+            (info, None)
+        }
+        Err(e) => {
+            info.error = Some(AppError::parse_json(&e, path).to_string());
+            (info, None)
+        }
+    }
+}
+
+/// File behind a layer the app may write to. Managed settings belong to the
+/// administrator, and project layers need a project.
+pub fn editable_layer_path(layer: SettingsLayer, project: Option<&Path>) -> AppResult<PathBuf> {
+    let project_file = |name: &str| {
+        project
+            .map(|p| p.join(".claude").join(name))
+            .ok_or_else(|| AppError::validation("projectPath", "A project is required for this scope"))
+    };
+    match layer {
+        SettingsLayer::User => Ok(ClaudeAdapter.root_dir()?.join("settings.json")),
+        SettingsLayer::Project => project_file("settings.json"),
+        SettingsLayer::Local => project_file("settings.local.json"),
+        SettingsLayer::Managed => Err(AppError::validation("scope", "Managed settings are read-only")),
+    }
+}
+
+/// Read-modify-write of one settings file as a JSON object. A missing file
+/// starts out empty; the previous content is snapshotted before writing.
+pub fn edit_layer<T>(
+    path: &Path,
+    edit: impl FnOnce(&mut serde_json::Map<String, serde_json::Value>) -> AppResult<T>,
+) -> AppResult<T> {
+    let mut map = match fs::read_to_string(path) {
+        Ok(content) if content.trim().is_empty() => serde_json::Map::new(),
+        Ok(content) => match serde_json::from_str(&content).map_err(|e| AppError::parse_json(&e, path))? {
+            serde_json::Value::Object(map) => map,
+            _ => {
+                return Err(AppError::Parse {
+                    message: format!("{}: settings must be a JSON object", path.display()),
+                    path: Some(path.to_string_lossy().to_string()),
+                    line: None,
+                    column: None,
+                })
+            }
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
+        Err(e) => return Err(AppError::io(e, path)),
+    };
+
+    let result = edit(&mut map)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| AppError::io(e, parent))?;
+    }
+    let json = serde_json::to_string_pretty(&map).map_err(|e| AppError::other(e.to_string()))?;
+    create_backup(path, Action::Save)?;
+    files::write_atomic(path, json.as_bytes())?;
+    Ok(result)
+}
+
+// ============================================================================
+// Merging
+// ============================================================================
+
+/// Arrays the CLI concatenates across layers instead of replacing.
+fn is_merged_array(path: &[String]) -> bool {
+    match path {
+        [root, key] if root == "permissions" => {
+            matches!(key.as_str(), "allow" | "deny" | "ask" | "additionalDirectories")
+        }
+        [root, _event] => root == "hooks",
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct Merger {
+    settings: serde_json::Map<String, serde_json::Value>,
+    origins: BTreeMap<Vec<String>, KeyOrigin>,
+}
+
+impl Merger {
+    fn apply(&mut self, layer: SettingsLayer, map: serde_json::Map<String, serde_json::Value>) {
+        let mut path = Vec::new();
+        self.walk(layer, map, &mut path);
+    }
+
+    fn walk(&mut self, layer: SettingsLayer, map: serde_json::Map<String, serde_json::Value>, path: &mut Vec<String>) {
+        for (key, value) in map {
+            path.push(key);
+            match value {
+                serde_json::Value::Object(child) if !child.is_empty() => self.walk(layer, child, path),
+                value => self.set(layer, path, value),
+            }
+            path.pop();
+        }
+    }
+
+    fn set(&mut self, layer: SettingsLayer, path: &[String], value: serde_json::Value) {
+        // A leaf replaces anything above or below it that a lower layer set
+        // with a different shape (e.g. `"env": null` vs `"env": {...}`).
+        let mut overrides = Vec::new();
+        let displaced: Vec<Vec<String>> = self
+            .origins
+            .keys()
+            .filter(|k| k.as_slice() != path && (k.starts_with(path) || path.starts_with(k)))
+            .cloned()
+            .collect();
+        for key in displaced {
+            if let Some(origin) = self.origins.remove(&key) {
+                push_unique(&mut overrides, &origin.overrides);
+                push_unique(&mut overrides, &[origin.layer]);
+            }
+        }
+
+        let mut merged_from = Vec::new();
+        let mut value = value;
+        if let Some(previous) = self.origins.remove(path) {
+            let existing = lookup(&self.settings, path).cloned();
+            match (existing, &mut value) {
+                (Some(serde_json::Value::Array(mut lower)), serde_json::Value::Array(higher)) if is_merged_array(path) => {
+                    for item in higher.drain(..) {
+                        if !lower.contains(&item) {
+                            lower.push(item);
+                        }
+                    }
+                    value = serde_json::Value::Array(lower);
+                    merged_from = if previous.merged_from.is_empty() {
+                        vec![previous.layer]
+                    } else {
+                        previous.merged_from
+                    };
+                    overrides = previous.overrides;
+                }
+                _ => {
+                    push_unique(&mut overrides, &previous.overrides);
+                    push_unique(&mut overrides, &[previous.layer]);
+                }
+            }
+        }
+        if !merged_from.is_empty() {
+            merged_from.push(layer);
+        }
+
+        insert(&mut self.settings, path, value);
+        self.origins.insert(
+            path.to_vec(),
+            KeyOrigin {
+                key: path.join("."),
+                path: path.to_vec(),
+                layer,
+                overrides,
+                merged_from,
+            },
+        );
+    }
+}
+
+fn push_unique(into: &mut Vec<SettingsLayer>, layers: &[SettingsLayer]) {
+    for layer in layers {
+        if !into.contains(layer) {
+            into.push(*layer);
+        }
+    }
+}
+
+fn lookup<'a>(map: &'a serde_json::Map<String, serde_json::Value>, path: &[String]) -> Option<&'a serde_json::Value> {
+    let (last, parents) = path.split_last()?;
+    let mut current = map;
+    for key in parents {
+        current = current.get(key)?.as_object()?;
+    }
+    current.get(last)
+}
+
+fn insert(map: &mut serde_json::Map<String, serde_json::Value>, path: &[String], value: serde_json::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut current = map;
+    for key in parents {
+        let entry = current
+            .entry(key.clone())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        if !entry.is_object() {
+            *entry = serde_json::Value::Object(Default::default());
+        }
+        current = entry.as_object_mut().unwrap();
+    }
+    current.insert(last.clone(), value);
+}
+
+/// Merges every layer that applies to `project`.
+pub fn effective_settings(project: Option<&Path>) -> AppResult<EffectiveSettings> {
+    let mut merger = Merger::default();
+    let mut layers = Vec::new();
+    for (layer, path) in layer_paths(project)? {
+        let (info, map) = read_layer(layer, &path);
+        if let Some(map) = map {
+            merger.apply(layer, map);
+        }
+        layers.push(info);
+    }
+
+    Ok(EffectiveSettings {
+        project_path: project.map(|p| p.to_string_lossy().to_string()),
+        settings: serde_json::Value::Object(merger.settings),
+        layers,
+        keys: merger.origins.into_values().collect(),
+    })
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// The settings Claude Code would run with in `project_path` (or outside any
+/// project when `None`), with the layer each key came from.
+#[tauri::command]
+pub fn claude_get_effective_settings(project_path: Option<String>) -> AppResult<EffectiveSettings> {
+    let project = project_path.as_deref().map(project_dir).transpose()?;
+    effective_settings(project.as_deref())
+}
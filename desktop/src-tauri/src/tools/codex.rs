@@ -0,0 +1,61 @@
+use std::path::{Path, PathBuf};
+
+use super::{ConfigFormat, HistoryEntry, SettingsFile, SkillLayout, ToolAdapter};
+
+// ============================================================================
+// Adapter
+// ============================================================================
+
+pub struct CodexAdapter;
+
+impl ToolAdapter for CodexAdapter {
+    fn id(&self) -> &'static str {
+        "codex"
+    }
+
+    fn name(&self) -> &'static str {
+        "Codex"
+    }
+
+    fn default_dir(&self) -> &'static str {
+        ".codex"
+    }
+
+    fn env_var(&self) -> Option<&'static str> {
+        Some("CODEX_HOME")
+    }
+
+    fn settings_files(&self, root: &Path) -> Vec<SettingsFile> {
+        vec![SettingsFile {
+            scope: "config",
+            path: root.join("config.toml"),
+            format: ConfigFormat::Toml,
+        }]
+    }
+
+    fn skills_dir(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("skills"))
+    }
+
+    fn skill_layout(&self) -> SkillLayout {
+        SkillLayout::Directory
+    }
+
+    fn prompts_dir(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("prompts"))
+    }
+
+    fn history_file(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("history.jsonl"))
+    }
+
+    fn parse_history_entry(&self, id: usize, v: &serde_json::Value) -> Option<HistoryEntry> {
+        Some(HistoryEntry {
+            id,
+            text: v["text"].as_str().unwrap_or("").to_string(),
+            timestamp: v["ts"].as_i64().unwrap_or(0) * 1000, // Convert to ms
+            session_id: v["session_id"].as_str().unwrap_or("").to_string(),
+            project: None,
+        })
+    }
+}
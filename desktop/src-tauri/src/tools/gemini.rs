@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{ConfigFormat, Session, SessionDetail, SettingsFile, ToolAdapter};
+use crate::backups::{create_backup, Action};
+use crate::error::{AppError, AppResult};
+use crate::{files, paths};
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+pub struct Extension {
+    name: String,
+    enabled: bool,
+    overrides: Option<Vec<String>>,
+}
+
+// ============================================================================
+// Adapter
+// ============================================================================
+
+pub struct GeminiAdapter;
+
+impl ToolAdapter for GeminiAdapter {
+    fn id(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn name(&self) -> &'static str {
+        "Gemini CLI"
+    }
+
+    fn default_dir(&self) -> &'static str {
+        ".gemini"
+    }
+
+    /// Gemini CLI treats `GEMINI_CLI_HOME` as a replacement home directory.
+    fn env_var(&self) -> Option<&'static str> {
+        Some("GEMINI_CLI_HOME")
+    }
+
+    fn root_from_env(&self, value: &str) -> PathBuf {
+        PathBuf::from(value).join(".gemini")
+    }
+
+    fn settings_files(&self, root: &Path) -> Vec<SettingsFile> {
+        vec![SettingsFile {
+            scope: "settings",
+            path: root.join("settings.json"),
+            format: ConfigFormat::Json,
+        }]
+    }
+
+    fn prompts_dir(&self, root: &Path) -> Option<PathBuf> {
+        Some(root.join("commands"))
+    }
+
+    fn prompt_extension(&self) -> &'static str {
+        "toml"
+    }
+
+    fn prompts_nested(&self) -> bool {
+        true
+    }
+
+    fn features(&self, _root: &Path) -> Vec<&'static str> {
+        vec!["settings", "prompts", "sessions", "extensions"]
+    }
+
+    fn list_sessions(&self) -> AppResult<Vec<Session>> {
+        let tmp_dir = self.root_dir()?.join("tmp");
+        let mut sessions = Vec::new();
+
+        if let Ok(projects) = fs::read_dir(&tmp_dir) {
+            for project in projects.filter_map(|e| e.ok()) {
+                if project.path().is_dir() {
+                    let project_hash = project.file_name().to_string_lossy().to_string();
+                    let chats_dir = project.path().join("chats");
+
+                    if let Ok(chats) = fs::read_dir(&chats_dir) {
+                        for chat in chats.filter_map(|e| e.ok()) {
+                            let name = chat.file_name().to_string_lossy().to_string();
+                            if name.ends_with(".json") {
+                                let session_id = name.trim_end_matches(".json").to_string();
+
+                                // Parse timestamp from filename
+                                let timestamp = parse_gemini_session_timestamp(&session_id);
+
+                                sessions.push(Session {
+                                    project_hash: project_hash.clone(),
+                                    session_id,
+                                    timestamp,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.timestamp));
+        Ok(sessions)
+    }
+
+    fn get_session(&self, project_hash: &str, session_id: &str) -> AppResult<SessionDetail> {
+        let relative = format!(
+            "tmp/{}/chats/{}.json",
+            paths::segment(project_hash)?,
+            paths::segment(session_id)?
+        );
+        let session_path = paths::resolve_within(&self.root_dir()?, &relative)?;
+
+        let content = fs::read_to_string(&session_path).map_err(|e| AppError::io(e, &session_path))?;
+        let session = serde_json::from_str(&content).map_err(|e| AppError::parse_json(&e, &session_path))?;
+
+        Ok(SessionDetail {
+            project_hash: project_hash.to_string(),
+            session_id: session_id.to_string(),
+            session,
+        })
+    }
+}
+
+fn parse_gemini_session_timestamp(session_id: &str) -> i64 {
+    // session-2026-01-08T20-46-3ba7254a
+    if let Some(caps) = session_id
+        .strip_prefix("session-")
+        .and_then(|s| s.get(..16))
+    {
+        // 2026-01-08T20-46
+        let formatted = caps.replace("T", " ").replace("-", ":");
+        // Try to parse as date
+        if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(
+            &format!("{}:00", formatted.replace(" ", "T").replace(":", "-").get(..10).unwrap_or("")),
+            "%Y-%m-%dT%H:%M:%S",
+        ) {
+            return dt.and_utc().timestamp_millis();
+        }
+    }
+    0
+}
+
+// ============================================================================
+// Gemini-only Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn gemini_get_extensions() -> AppResult<Vec<Extension>> {
+    let ext_dir = GeminiAdapter.root_dir()?.join("extensions");
+    let enablement_path = ext_dir.join("extension-enablement.json");
+
+    let enablement: std::collections::HashMap<String, bool> =
+        fs::read_to_string(&enablement_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+    let mut results = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&ext_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            if entry.path().is_dir() {
+                let name = entry.file_name().to_string_lossy().to_string();
+
+                // Try to read manifest for overrides
+                let manifest_path = entry.path().join("manifest.json");
+                let overrides: Option<Vec<String>> = fs::read_to_string(&manifest_path)
+                    .ok()
+                    .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+                    .and_then(|v| {
+                        v.get("overrides")
+                            .and_then(|o| o.as_array())
+                            .map(|arr| {
+                                arr.iter()
+                                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                    .collect()
+                            })
+                    });
+
+                results.push(Extension {
+                    name: name.clone(),
+                    enabled: *enablement.get(&name).unwrap_or(&false),
+                    overrides,
+                });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+#[tauri::command]
+pub fn gemini_toggle_extension(name: String, enabled: bool) -> AppResult<bool> {
+    let ext_dir = GeminiAdapter.root_dir()?.join("extensions");
+    let enablement_path = ext_dir.join("extension-enablement.json");
+
+    // Read current state
+    let mut enablement: std::collections::HashMap<String, bool> =
+        fs::read_to_string(&enablement_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+
+    // Update the extension state
+    enablement.insert(name, enabled);
+
+    // Write back
+    create_backup(&enablement_path, Action::Save)?;
+    let json = serde_json::to_string_pretty(&enablement).map_err(|e| AppError::other(e.to_string()))?;
+    files::write_atomic(&enablement_path, json.as_bytes())?;
+
+    Ok(true)
+}
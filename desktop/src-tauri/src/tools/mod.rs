@@ -0,0 +1,712 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::backups::{create_backup, Action};
+use crate::config;
+use crate::error::{AppError, AppResult};
+use crate::files::{self, FileVersion};
+use crate::paths;
+
+pub mod claude;
+pub mod codex;
+pub mod gemini;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+pub struct ConfigResult {
+    pub config: serde_json::Value,
+    pub raw: String,
+    pub version: FileVersion,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Prompt {
+    pub path: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PromptContent {
+    pub path: String,
+    pub content: String,
+    pub version: FileVersion,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Skill {
+    pub name: String,
+    #[serde(rename = "isSystem")]
+    pub is_system: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SkillContent {
+    pub name: String,
+    pub content: String,
+    pub version: FileVersion,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: usize,
+    pub text: String,
+    pub timestamp: i64,
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub project: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HistoryResult {
+    pub entries: Vec<HistoryEntry>,
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+    pub sessions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// Opaque per-tool project key (the project hash for Gemini).
+    #[serde(rename = "projectHash")]
+    pub project_hash: String,
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SessionDetail {
+    #[serde(rename = "projectHash")]
+    pub project_hash: String,
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub session: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ToolInfo {
+    id: String,
+    name: String,
+    #[serde(rename = "rootDir")]
+    root_dir: Option<String>,
+    #[serde(rename = "rootSource")]
+    root_source: Option<config::RootSource>,
+    installed: bool,
+    features: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    pub fn validate(self, content: &str) -> AppResult<()> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str::<serde_json::Value>(content)
+                .map(|_| ())
+                .map_err(|e| AppError::invalid_json(&e)),
+            ConfigFormat::Toml => toml::from_str::<toml::Value>(content)
+                .map(|_| ())
+                .map_err(|e| AppError::invalid_toml(&e, content)),
+        }
+    }
+
+    pub fn parse(self, content: &str) -> serde_json::Value {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(content).unwrap_or(serde_json::json!({})),
+            ConfigFormat::Toml => toml::from_str(content)
+                .map(|v: toml::Value| serde_json::to_value(v).unwrap_or(serde_json::json!({})))
+                .unwrap_or(serde_json::json!({})),
+        }
+    }
+}
+
+pub struct SettingsFile {
+    pub scope: &'static str,
+    pub path: PathBuf,
+    pub format: ConfigFormat,
+}
+
+/// How a tool stores its skills on disk.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SkillLayout {
+    /// `<skills>/<name>.md`
+    MarkdownFile,
+    /// `<skills>/<name>/SKILL.md`, with built-ins under `<skills>/.system/`
+    Directory,
+}
+
+pub struct HistoryQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub search: Option<String>,
+    pub session_id: Option<String>,
+}
+
+// ============================================================================
+// Adapter
+// ============================================================================
+
+/// Everything the app knows about one AI assistant's on-disk layout.
+///
+/// Only `id`, `name`, `default_dir` and `settings_files` are required. The
+/// directory/file hooks receive the resolved root, default to "unsupported"
+/// and drive the shared implementations below, so most adapters never
+/// override the `get_*`, `save_*` or `list_*` methods themselves.
+pub trait ToolAdapter: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    /// Directory name under the user's home, e.g. `.claude`.
+    fn default_dir(&self) -> &'static str;
+
+    /// Environment variable the CLI itself honours to relocate its home.
+    fn env_var(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Maps the value of `env_var` to the tool root.
+    fn root_from_env(&self, value: &str) -> PathBuf {
+        PathBuf::from(value)
+    }
+
+    /// The tool root after applying user overrides and environment variables.
+    fn root_dir(&self) -> AppResult<PathBuf> {
+        config::resolve_root(self).map(|r| r.path)
+    }
+
+    /// Settings files in priority order; the first one is the default scope.
+    fn settings_files(&self, root: &Path) -> Vec<SettingsFile>;
+
+    fn skills_dir(&self, _root: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn skill_layout(&self) -> SkillLayout {
+        SkillLayout::MarkdownFile
+    }
+
+    /// Directory holding prompts / slash commands.
+    fn prompts_dir(&self, _root: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn prompt_extension(&self) -> &'static str {
+        "md"
+    }
+
+    /// Whether prompts may live in nested folders (namespaced commands).
+    fn prompts_nested(&self) -> bool {
+        false
+    }
+
+    /// The append-only JSONL prompt log, if the tool keeps one.
+    fn history_file(&self, _root: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn parse_history_entry(&self, _id: usize, _line: &serde_json::Value) -> Option<HistoryEntry> {
+        None
+    }
+
+    fn features(&self, root: &Path) -> Vec<&'static str> {
+        let mut features = vec!["settings"];
+        if self.skills_dir(root).is_some() {
+            features.push("skills");
+        }
+        if self.prompts_dir(root).is_some() {
+            features.push("prompts");
+        }
+        if self.history_file(root).is_some() {
+            features.push("history");
+        }
+        features
+    }
+
+    // ------------------------------------------------------------------------
+    // Settings
+    // ------------------------------------------------------------------------
+
+    fn get_settings(&self) -> AppResult<serde_json::Value> {
+        let file = self
+            .settings_files(&self.root_dir()?)
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::not_found(format!("{} has no settings file", self.name())))?;
+        let (raw, version) = files::read_versioned(&file.path)?;
+        let config = file.format.parse(&raw);
+        serde_json::to_value(ConfigResult { config, raw, version }).map_err(|e| AppError::other(e.to_string()))
+    }
+
+    fn save_settings(
+        &self,
+        scope: Option<&str>,
+        content: &str,
+        expected: Option<&FileVersion>,
+    ) -> AppResult<FileVersion> {
+        let files = self.settings_files(&self.root_dir()?);
+        let file = match scope {
+            Some(scope) => files.into_iter().find(|f| f.scope == scope),
+            None => files.into_iter().next(),
+        }
+        .ok_or_else(|| AppError::validation("scope", format!("Unknown settings scope for {}", self.name())))?;
+
+        file.format.validate(content)?;
+        save_checked(&file.path, content, expected)
+    }
+
+    // ------------------------------------------------------------------------
+    // Skills
+    // ------------------------------------------------------------------------
+
+    fn list_skills(&self) -> AppResult<Vec<Skill>> {
+        let Some(skills_dir) = self.skills_dir(&self.root_dir()?) else {
+            return Ok(vec![]);
+        };
+        let mut results = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(&skills_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let name = entry.file_name().to_string_lossy().to_string();
+                match self.skill_layout() {
+                    SkillLayout::MarkdownFile => {
+                        if name.ends_with(".md") {
+                            results.push(Skill {
+                                name: name.trim_end_matches(".md").to_string(),
+                                is_system: false,
+                            });
+                        }
+                    }
+                    SkillLayout::Directory => {
+                        if !entry.path().is_dir() {
+                            continue;
+                        }
+                        if name == ".system" {
+                            for sys in list_dirs(&entry.path()) {
+                                results.push(Skill {
+                                    name: format!(".system/{}", sys),
+                                    is_system: true,
+                                });
+                            }
+                        } else {
+                            results.push(Skill {
+                                name,
+                                is_system: false,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(results)
+    }
+
+    /// Resolves a skill name to its markdown file, confined to the skills dir.
+    fn skill_path(&self, name: &str) -> AppResult<PathBuf> {
+        let skills_dir = self
+            .skills_dir(&self.root_dir()?)
+            .ok_or_else(|| AppError::not_found(format!("{} does not support skills", self.name())))?;
+        match self.skill_layout() {
+            SkillLayout::MarkdownFile => {
+                paths::resolve_within(&skills_dir, &format!("{}.md", paths::segment(name)?))
+            }
+            SkillLayout::Directory => {
+                let skill_path = paths::resolve_within(&skills_dir, name)?.join("SKILL.md");
+                paths::ensure_contained(&skills_dir, &skill_path)?;
+                Ok(skill_path)
+            }
+        }
+    }
+
+    fn get_skill(&self, name: &str) -> AppResult<SkillContent> {
+        let (content, version) = files::read_versioned(&self.skill_path(name)?)?;
+        Ok(SkillContent {
+            name: name.to_string(),
+            content,
+            version,
+        })
+    }
+
+    fn save_skill(
+        &self,
+        name: &str,
+        content: &str,
+        expected: Option<&FileVersion>,
+    ) -> AppResult<FileVersion> {
+        save_checked(&self.skill_path(name)?, content, expected)
+    }
+
+    fn create_skill(&self, name: &str, content: &str) -> AppResult<FileVersion> {
+        let skill_path = self.skill_path(name)?;
+        if skill_path.exists() {
+            return Err(AppError::already_exists(&skill_path, "Skill already exists"));
+        }
+        if let Some(parent) = skill_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(e, parent))?;
+        }
+        files::write_atomic(&skill_path, content.as_bytes())
+    }
+
+    fn delete_skill(&self, name: &str) -> AppResult<()> {
+        let skill_path = self.skill_path(name)?;
+        create_backup(&skill_path, Action::Delete)?;
+        match self.skill_layout() {
+            SkillLayout::MarkdownFile => fs::remove_file(&skill_path),
+            SkillLayout::Directory => fs::remove_dir_all(skill_path.parent().unwrap()),
+        }
+        .map_err(|e| AppError::io(e, &skill_path))
+    }
+
+    // ------------------------------------------------------------------------
+    // Prompts / commands
+    // ------------------------------------------------------------------------
+
+    fn list_prompts(&self) -> AppResult<Vec<Prompt>> {
+        let Some(prompts_dir) = self.prompts_dir(&self.root_dir()?) else {
+            return Ok(vec![]);
+        };
+        let mut results = Vec::new();
+        collect_prompts(
+            &prompts_dir,
+            "",
+            &format!(".{}", self.prompt_extension()),
+            self.prompts_nested(),
+            &mut results,
+        );
+        results.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(results)
+    }
+
+    /// Resolves a prompt's relative path, confined to the prompts dir and
+    /// limited to the tool's prompt file extension.
+    fn prompt_path(&self, path: &str) -> AppResult<PathBuf> {
+        let prompts_dir = self
+            .prompts_dir(&self.root_dir()?)
+            .ok_or_else(|| AppError::not_found(format!("{} does not support prompts", self.name())))?;
+        if !path.ends_with(&format!(".{}", self.prompt_extension())) {
+            return Err(AppError::validation(
+                "path",
+                format!("Prompt files must end in .{}", self.prompt_extension()),
+            ));
+        }
+        if !self.prompts_nested() {
+            paths::segment(path)?;
+        }
+        paths::resolve_within(&prompts_dir, path)
+    }
+
+    fn get_prompt(&self, path: &str) -> AppResult<PromptContent> {
+        let (content, version) = files::read_versioned(&self.prompt_path(path)?)?;
+        Ok(PromptContent {
+            path: path.to_string(),
+            content,
+            version,
+        })
+    }
+
+    fn save_prompt(
+        &self,
+        path: &str,
+        content: &str,
+        expected: Option<&FileVersion>,
+    ) -> AppResult<FileVersion> {
+        save_checked(&self.prompt_path(path)?, content, expected)
+    }
+
+    fn create_prompt(&self, path: &str, content: &str) -> AppResult<FileVersion> {
+        let file_path = self.prompt_path(path)?;
+        if file_path.exists() {
+            return Err(AppError::already_exists(&file_path, "Prompt already exists"));
+        }
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(e, parent))?;
+        }
+        files::write_atomic(&file_path, content.as_bytes())
+    }
+
+    fn delete_prompt(&self, path: &str) -> AppResult<()> {
+        let file_path = self.prompt_path(path)?;
+        create_backup(&file_path, Action::Delete)?;
+        fs::remove_file(&file_path).map_err(|e| AppError::io(e, &file_path))
+    }
+
+    // ------------------------------------------------------------------------
+    // History & sessions
+    // ------------------------------------------------------------------------
+
+    fn get_history(&self, query: &HistoryQuery) -> AppResult<HistoryResult> {
+        let limit = query.limit.unwrap_or(50);
+        let offset = query.offset.unwrap_or(0);
+
+        let content = self
+            .history_file(&self.root_dir()?)
+            .and_then(|p| fs::read_to_string(p).ok())
+            .unwrap_or_default();
+
+        let mut entries: Vec<HistoryEntry> = content
+            .lines()
+            .filter(|l| !l.is_empty())
+            .enumerate()
+            .filter_map(|(idx, line)| {
+                serde_json::from_str::<serde_json::Value>(line)
+                    .ok()
+                    .and_then(|v| self.parse_history_entry(idx, &v))
+            })
+            .collect();
+
+        // Get unique sessions
+        let mut sessions: Vec<String> = entries
+            .iter()
+            .filter(|e| !e.session_id.is_empty())
+            .map(|e| e.session_id.clone())
+            .collect::<std::collections::HashSet<_>>()
+            .into_iter()
+            .collect();
+        sessions.sort();
+
+        entries.reverse(); // Newest first
+
+        // Filter by search
+        if let Some(ref s) = query.search {
+            let s_lower = s.to_lowercase();
+            entries.retain(|e| {
+                e.text.to_lowercase().contains(&s_lower)
+                    || e.project.as_ref().map(|p| p.to_lowercase().contains(&s_lower)).unwrap_or(false)
+            });
+        }
+
+        // Filter by session
+        if let Some(ref sid) = query.session_id {
+            entries.retain(|e| &e.session_id == sid);
+        }
+
+        let total = entries.len();
+        let paginated: Vec<_> = entries.into_iter().skip(offset).take(limit).collect();
+
+        Ok(HistoryResult {
+            entries: paginated,
+            total,
+            limit,
+            offset,
+            sessions,
+        })
+    }
+
+    fn list_sessions(&self) -> AppResult<Vec<Session>> {
+        Ok(vec![])
+    }
+
+    fn get_session(&self, _project_hash: &str, _session_id: &str) -> AppResult<SessionDetail> {
+        Err(AppError::not_found(format!("{} does not expose sessions", self.name())))
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+static ADAPTERS: &[&dyn ToolAdapter] = &[
+    &claude::ClaudeAdapter,
+    &codex::CodexAdapter,
+    &gemini::GeminiAdapter,
+];
+
+pub fn adapters() -> &'static [&'static dyn ToolAdapter] {
+    ADAPTERS
+}
+
+pub fn adapter(tool: &str) -> AppResult<&'static dyn ToolAdapter> {
+    ADAPTERS
+        .iter()
+        .copied()
+        .find(|a| a.id() == tool)
+        .ok_or_else(|| AppError::not_found(format!("Unknown tool: {}", tool)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Conflict check, snapshot, then atomic write: the path every editor save takes.
+fn save_checked(path: &Path, content: &str, expected: Option<&FileVersion>) -> AppResult<FileVersion> {
+    files::check_version(path, expected)?;
+    create_backup(path, Action::Save)?;
+    files::write_atomic(path, content.as_bytes())
+}
+
+fn list_dirs(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn collect_prompts(dir: &Path, base: &str, suffix: &str, nested: bool, results: &mut Vec<Prompt>) {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let relative = if base.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", base, name)
+            };
+
+            if path.is_dir() {
+                if nested {
+                    collect_prompts(&path, &relative, suffix, nested, results);
+                }
+            } else if name.ends_with(suffix) {
+                results.push(Prompt {
+                    path: relative,
+                    name: name.trim_end_matches(suffix).to_string(),
+                });
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn tool_list() -> Vec<ToolInfo> {
+    adapters()
+        .iter()
+        .map(|a| {
+            let resolved = config::resolve_root(*a).ok();
+            ToolInfo {
+                id: a.id().to_string(),
+                name: a.name().to_string(),
+                root_dir: resolved.as_ref().map(|r| r.path.to_string_lossy().to_string()),
+                root_source: resolved.as_ref().map(|r| r.source),
+                installed: resolved.as_ref().map(|r| r.path.is_dir()).unwrap_or(false),
+                features: resolved
+                    .as_ref()
+                    .map(|r| a.features(&r.path).into_iter().map(String::from).collect())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn tool_get_settings(tool: String) -> AppResult<serde_json::Value> {
+    adapter(&tool)?.get_settings()
+}
+
+/// Saves a settings file. Pass the `version` from `tool_get_settings` as
+/// `expected` to fail with a conflict if the file changed since it was read.
+#[tauri::command]
+pub fn tool_save_settings(
+    tool: String,
+    scope: Option<String>,
+    content: String,
+    expected: Option<FileVersion>,
+) -> AppResult<FileVersion> {
+    adapter(&tool)?.save_settings(scope.as_deref(), &content, expected.as_ref())
+}
+
+#[tauri::command]
+pub fn tool_get_skills(tool: String) -> AppResult<Vec<Skill>> {
+    adapter(&tool)?.list_skills()
+}
+
+#[tauri::command]
+pub fn tool_get_skill(tool: String, name: String) -> AppResult<SkillContent> {
+    adapter(&tool)?.get_skill(&name)
+}
+
+#[tauri::command]
+pub fn tool_save_skill(
+    tool: String,
+    name: String,
+    content: String,
+    expected: Option<FileVersion>,
+) -> AppResult<FileVersion> {
+    adapter(&tool)?.save_skill(&name, &content, expected.as_ref())
+}
+
+#[tauri::command]
+pub fn tool_create_skill(tool: String, name: String, content: String) -> AppResult<FileVersion> {
+    adapter(&tool)?.create_skill(&name, &content)
+}
+
+#[tauri::command]
+pub fn tool_delete_skill(tool: String, name: String) -> AppResult<bool> {
+    adapter(&tool)?.delete_skill(&name)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn tool_get_prompts(tool: String) -> AppResult<Vec<Prompt>> {
+    adapter(&tool)?.list_prompts()
+}
+
+#[tauri::command]
+pub fn tool_get_prompt(tool: String, path: String) -> AppResult<PromptContent> {
+    adapter(&tool)?.get_prompt(&path)
+}
+
+#[tauri::command]
+pub fn tool_save_prompt(
+    tool: String,
+    path: String,
+    content: String,
+    expected: Option<FileVersion>,
+) -> AppResult<FileVersion> {
+    adapter(&tool)?.save_prompt(&path, &content, expected.as_ref())
+}
+
+#[tauri::command]
+pub fn tool_create_prompt(tool: String, path: String, content: String) -> AppResult<FileVersion> {
+    adapter(&tool)?.create_prompt(&path, &content)
+}
+
+#[tauri::command]
+pub fn tool_delete_prompt(tool: String, path: String) -> AppResult<bool> {
+    adapter(&tool)?.delete_prompt(&path)?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn tool_get_history(
+    tool: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    search: Option<String>,
+    session_id: Option<String>,
+) -> AppResult<HistoryResult> {
+    adapter(&tool)?.get_history(&HistoryQuery {
+        limit,
+        offset,
+        search,
+        session_id,
+    })
+}
+
+#[tauri::command]
+pub fn tool_get_sessions(tool: String) -> AppResult<Vec<Session>> {
+    adapter(&tool)?.list_sessions()
+}
+
+#[tauri::command]
+pub fn tool_get_session(tool: String, project_hash: String, session_id: String) -> AppResult<SessionDetail> {
+    adapter(&tool)?.get_session(&project_hash, &session_id)
+}
@@ -3,6 +3,25 @@ import { invoke } from '@tauri-apps/api/core';
 // Detect if running in Tauri (check for __TAURI_INTERNALS__ which is set in Tauri v2)
 const isTauri = typeof window !== 'undefined' && '__TAURI_INTERNALS__' in window;
 
+// Shape of every error rejected by a Tauri command (see src-tauri/src/error.rs)
+export type AppErrorKind = 'notFound' | 'conflict' | 'validation' | 'permissionDenied' | 'io' | 'parse';
+
+export interface AppError {
+  kind: AppErrorKind;
+  message: string;
+  path?: string | null;
+  field?: string | null;
+  line?: number | null;
+  column?: number | null;
+  expected?: { hash: string; mtime: number } | null;
+  actual?: { hash: string; mtime: number } | null;
+  content?: string | null;
+}
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === 'object' && e !== null && 'kind' in e && 'message' in e;
+}
+
 // ============================================================================
 // Web API helpers (fallback for non-Tauri)
 // ============================================================================
@@ -67,14 +86,14 @@ export const api = {
   claude: {
     getSettings: async () => {
       if (isTauri) {
-        return invoke<{ settings: Record<string, unknown>; local: Record<string, unknown> }>('claude_get_settings');
+        return invoke<{ settings: Record<string, unknown>; local: Record<string, unknown> }>('tool_get_settings', { tool: 'claude' });
       }
       return fetchJson<{ settings: Record<string, unknown>; local: Record<string, unknown> }>('/claude/settings');
     },
 
     saveSettings: async (type: 'settings' | 'local', content: string) => {
       if (isTauri) {
-        await invoke('claude_save_settings', { settingsType: type, content });
+        await invoke('tool_save_settings', { tool: 'claude', scope: type, content });
         return { success: true };
       }
       return putJson<{ success: boolean }>('/claude/settings', { type, content });
@@ -82,21 +101,21 @@ export const api = {
 
     getCommands: async () => {
       if (isTauri) {
-        return invoke<Array<{ path: string; name: string }>>('claude_get_commands');
+        return invoke<Array<{ path: string; name: string }>>('tool_get_prompts', { tool: 'claude' });
       }
       return fetchJson<Array<{ path: string; name: string }>>('/claude/commands');
     },
 
     getCommand: async (path: string) => {
       if (isTauri) {
-        return invoke<{ path: string; content: string }>('claude_get_command', { path });
+        return invoke<{ path: string; content: string }>('tool_get_prompt', { tool: 'claude', path });
       }
       return fetchJson<{ path: string; content: string }>(`/claude/commands/${encodeURIComponent(path)}`);
     },
 
     saveCommand: async (path: string, content: string) => {
       if (isTauri) {
-        await invoke('claude_save_command', { path, content });
+        await invoke('tool_save_prompt', { tool: 'claude', path, content });
         return { success: true };
       }
       return putJson<{ success: boolean }>(`/claude/commands/${encodeURIComponent(path)}`, { content });
@@ -104,7 +123,7 @@ export const api = {
 
     createCommand: async (path: string, content: string) => {
       if (isTauri) {
-        await invoke('claude_create_command', { path, content });
+        await invoke('tool_create_prompt', { tool: 'claude', path, content });
         return { success: true };
       }
       return postJson<{ success: boolean }>('/claude/commands', { path, content });
@@ -112,7 +131,7 @@ export const api = {
 
     deleteCommand: async (path: string) => {
       if (isTauri) {
-        await invoke('claude_delete_command', { path });
+        await invoke('tool_delete_prompt', { tool: 'claude', path });
         return { success: true };
       }
       return deleteJson<{ success: boolean }>(`/claude/commands/${encodeURIComponent(path)}`);
@@ -127,21 +146,21 @@ export const api = {
 
     getSkills: async () => {
       if (isTauri) {
-        return invoke<Array<{ name: string; isSystem: boolean }>>('claude_get_skills');
+        return invoke<Array<{ name: string; isSystem: boolean }>>('tool_get_skills', { tool: 'claude' });
       }
       return fetchJson<Array<{ name: string; isSystem: boolean }>>('/claude/skills');
     },
 
     getSkill: async (name: string) => {
       if (isTauri) {
-        return invoke<{ name: string; content: string }>('claude_get_skill', { name });
+        return invoke<{ name: string; content: string }>('tool_get_skill', { tool: 'claude', name });
       }
       return fetchJson<{ name: string; content: string }>(`/claude/skills/${encodeURIComponent(name)}`);
     },
 
     saveSkill: async (name: string, content: string) => {
       if (isTauri) {
-        await invoke('claude_save_skill', { name, content });
+        await invoke('tool_save_skill', { tool: 'claude', name, content });
         return { success: true };
       }
       return putJson<{ success: boolean }>(`/claude/skills/${encodeURIComponent(name)}`, { content });
@@ -149,7 +168,7 @@ export const api = {
 
     createSkill: async (name: string, content: string) => {
       if (isTauri) {
-        await invoke('claude_create_skill', { name, content });
+        await invoke('tool_create_skill', { tool: 'claude', name, content });
         return { success: true };
       }
       return postJson<{ success: boolean }>('/claude/skills', { name, content });
@@ -157,7 +176,7 @@ export const api = {
 
     deleteSkill: async (name: string) => {
       if (isTauri) {
-        await invoke('claude_delete_skill', { name });
+        await invoke('tool_delete_skill', { tool: 'claude', name });
         return { success: true };
       }
       return deleteJson<{ success: boolean }>(`/claude/skills/${encodeURIComponent(name)}`);
@@ -171,7 +190,8 @@ export const api = {
           limit: number;
           offset: number;
           sessions: string[];
-        }>('claude_get_history', {
+        }>('tool_get_history', {
+          tool: 'claude',
           limit: params?.limit,
           offset: params?.offset,
           search: params?.search,
@@ -196,14 +216,14 @@ export const api = {
   codex: {
     getConfig: async () => {
       if (isTauri) {
-        return invoke<{ config: Record<string, unknown>; raw: string }>('codex_get_config');
+        return invoke<{ config: Record<string, unknown>; raw: string }>('tool_get_settings', { tool: 'codex' });
       }
       return fetchJson<{ config: Record<string, unknown>; raw: string }>('/codex/config');
     },
 
     saveConfig: async (content: string) => {
       if (isTauri) {
-        await invoke('codex_save_config', { content });
+        await invoke('tool_save_settings', { tool: 'codex', content });
         return { success: true };
       }
       return putJson<{ success: boolean }>('/codex/config', { content });
@@ -211,21 +231,21 @@ export const api = {
 
     getSkills: async () => {
       if (isTauri) {
-        return invoke<Array<{ name: string; isSystem: boolean }>>('codex_get_skills');
+        return invoke<Array<{ name: string; isSystem: boolean }>>('tool_get_skills', { tool: 'codex' });
       }
       return fetchJson<Array<{ name: string; isSystem: boolean }>>('/codex/skills');
     },
 
     getSkill: async (name: string) => {
       if (isTauri) {
-        return invoke<{ name: string; content: string }>('codex_get_skill', { name });
+        return invoke<{ name: string; content: string }>('tool_get_skill', { tool: 'codex', name });
       }
       return fetchJson<{ name: string; content: string }>(`/codex/skills/${encodeURIComponent(name)}`);
     },
 
     saveSkill: async (name: string, content: string) => {
       if (isTauri) {
-        await invoke('codex_save_skill', { name, content });
+        await invoke('tool_save_skill', { tool: 'codex', name, content });
         return { success: true };
       }
       return putJson<{ success: boolean }>(`/codex/skills/${encodeURIComponent(name)}`, { content });
@@ -233,7 +253,7 @@ export const api = {
 
     createSkill: async (name: string, content: string) => {
       if (isTauri) {
-        await invoke('codex_create_skill', { name, content });
+        await invoke('tool_create_skill', { tool: 'codex', name, content });
         return { success: true };
       }
       return postJson<{ success: boolean }>('/codex/skills', { name, content });
@@ -241,7 +261,7 @@ export const api = {
 
     deleteSkill: async (name: string) => {
       if (isTauri) {
-        await invoke('codex_delete_skill', { name });
+        await invoke('tool_delete_skill', { tool: 'codex', name });
         return { success: true };
       }
       return deleteJson<{ success: boolean }>(`/codex/skills/${encodeURIComponent(name)}`);
@@ -249,21 +269,21 @@ export const api = {
 
     getPrompts: async () => {
       if (isTauri) {
-        return invoke<Array<{ name: string; path: string }>>('codex_get_prompts');
+        return invoke<Array<{ name: string; path: string }>>('tool_get_prompts', { tool: 'codex' });
       }
       return fetchJson<Array<{ name: string; path: string }>>('/codex/prompts');
     },
 
     getPrompt: async (path: string) => {
       if (isTauri) {
-        return invoke<{ path: string; content: string }>('codex_get_prompt', { path });
+        return invoke<{ path: string; content: string }>('tool_get_prompt', { tool: 'codex', path });
       }
       return fetchJson<{ path: string; content: string }>(`/codex/prompts/${encodeURIComponent(path)}`);
     },
 
     savePrompt: async (path: string, content: string) => {
       if (isTauri) {
-        await invoke('codex_save_prompt', { path, content });
+        await invoke('tool_save_prompt', { tool: 'codex', path, content });
         return { success: true };
       }
       return putJson<{ success: boolean }>(`/codex/prompts/${encodeURIComponent(path)}`, { content });
@@ -271,7 +291,7 @@ export const api = {
 
     createPrompt: async (path: string, content: string) => {
       if (isTauri) {
-        await invoke('codex_create_prompt', { path, content });
+        await invoke('tool_create_prompt', { tool: 'codex', path, content });
         return { success: true };
       }
       return postJson<{ success: boolean }>('/codex/prompts', { path, content });
@@ -279,7 +299,7 @@ export const api = {
 
     deletePrompt: async (path: string) => {
       if (isTauri) {
-        await invoke('codex_delete_prompt', { path });
+        await invoke('tool_delete_prompt', { tool: 'codex', path });
         return { success: true };
       }
       return deleteJson<{ success: boolean }>(`/codex/prompts/${encodeURIComponent(path)}`);
@@ -293,7 +313,8 @@ export const api = {
           limit: number;
           offset: number;
           sessions: string[];
-        }>('codex_get_history', {
+        }>('tool_get_history', {
+          tool: 'codex',
           limit: params?.limit,
           offset: params?.offset,
           search: params?.search,
@@ -318,14 +339,14 @@ export const api = {
   gemini: {
     getSettings: async () => {
       if (isTauri) {
-        return invoke<{ settings: Record<string, unknown>; raw: string }>('gemini_get_settings');
+        return invoke<{ settings: Record<string, unknown>; raw: string }>('tool_get_settings', { tool: 'gemini' });
       }
       return fetchJson<{ settings: Record<string, unknown>; raw: string }>('/gemini/settings');
     },
 
     saveSettings: async (content: string) => {
       if (isTauri) {
-        await invoke('gemini_save_settings', { content });
+        await invoke('tool_save_settings', { tool: 'gemini', content });
         return { success: true };
       }
       return putJson<{ success: boolean }>('/gemini/settings', { content });
@@ -348,14 +369,14 @@ export const api = {
 
     getSessions: async () => {
       if (isTauri) {
-        return invoke<Array<{ projectHash: string; sessionId: string; timestamp: number }>>('gemini_get_sessions');
+        return invoke<Array<{ projectHash: string; sessionId: string; timestamp: number }>>('tool_get_sessions', { tool: 'gemini' });
       }
       return fetchJson<Array<{ projectHash: string; sessionId: string; timestamp: number }>>('/gemini/sessions');
     },
 
     getSession: async (projectHash: string, sessionId: string) => {
       if (isTauri) {
-        return invoke<{ projectHash: string; sessionId: string; session: unknown }>('gemini_get_session', { projectHash, sessionId });
+        return invoke<{ projectHash: string; sessionId: string; session: unknown }>('tool_get_session', { tool: 'gemini', projectHash, sessionId });
       }
       return fetchJson<{ projectHash: string; sessionId: string; session: unknown }>(`/gemini/sessions/${projectHash}/${sessionId}`);
     },